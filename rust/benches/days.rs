@@ -0,0 +1,228 @@
+//! Time each day's parse step and both parts separately, on the checked-in inputs.
+//!
+//! Usage: `cargo bench --bench days -- [<day> | <first>-<last>]... [--time <secs>] [--save <path>]
+//! [--baseline <path>]`
+//!
+//! Each phase is repeated until it has run for `--time` seconds (1 by default), up to 100 times,
+//! and the median and fastest samples are reported. `--save` writes the medians to a file, and
+//! `--baseline` compares against a file written earlier, so that a redesign can be measured
+//! against the code it replaces.
+
+use aoc_2020::runner::DurationFmt;
+use aoc_2020::{Day, Puzzle, Res, DAYS};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant};
+
+const MAX_SAMPLES: usize = 100;
+
+struct Args {
+    /// Run every day if this is empty.
+    days: Vec<(u32, u32)>,
+    time: Duration,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+}
+
+/// The timings of one phase of one day.
+struct Bench {
+    day: u32,
+
+    /// `parse`, `part1`, or `part2`.
+    phase: &'static str,
+
+    samples: Vec<Duration>,
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Res<()> {
+    let args = Args::parse()?;
+    let baseline = match &args.baseline {
+        Some(path) => read_baseline(&fs::read_to_string(path)?)?,
+        None => HashMap::new(),
+    };
+
+    println!(
+        "{:>3}  {:<6}  {:>9}  {:>9}  {:>7}  vs baseline",
+        "Day", "Phase", "Median", "Min", "Samples"
+    );
+
+    let mut benches = vec![];
+    for day in DAYS.iter().filter(|d| args.includes(d.number)) {
+        for bench in bench_day(day, args.time)? {
+            let median = bench.median();
+            let change = match baseline.get(&(bench.day, bench.phase)) {
+                // Too fast to measure last time, so there's no meaningful ratio.
+                Some(&old) if old.is_zero() => String::from("(was 0ns)"),
+                Some(&old) => {
+                    let pct = (median.as_secs_f64() / old.as_secs_f64() - 1.0) * 100.0;
+                    format!("{:+.1}%", pct)
+                }
+                None => String::new(),
+            };
+            let row = format!(
+                "{:>3}  {:<6}  {:>9}  {:>9}  {:>7}  {}",
+                bench.day,
+                bench.phase,
+                DurationFmt(Some(median)),
+                DurationFmt(bench.samples.iter().min().copied()),
+                bench.samples.len(),
+                change
+            );
+            println!("{}", row.trim_end());
+            benches.push(bench);
+        }
+    }
+
+    if let Some(path) = &args.save {
+        fs::write(path, write_baseline(&benches))?;
+    }
+    Ok(())
+}
+
+/// Benchmark the parse step and each part of one day.
+fn bench_day(day: &Day, time: Duration) -> Res<Vec<Bench>> {
+    // Read the whole file up front, so that disk access isn't part of the parse time.
+    let input = fs::read(aoc_2020::input_path(day.number))?;
+
+    let mut benches = vec![];
+    let mut puzzle = None;
+    benches.push(Bench::run(day.number, "parse", time, || {
+        puzzle = Some(day.parse(&mut input.as_slice())?);
+        Ok(())
+    })?);
+
+    let puzzle: Box<dyn Puzzle> = puzzle.unwrap();
+    benches.push(Bench::run(day.number, "part1", time, || {
+        puzzle.part1()?;
+        Ok(())
+    })?);
+    if puzzle.has_part2() {
+        benches.push(Bench::run(day.number, "part2", time, || {
+            puzzle.part2()?;
+            Ok(())
+        })?);
+    }
+    Ok(benches)
+}
+
+impl Bench {
+    /// Call `f` repeatedly, until `time` is up or there are enough samples.
+    ///
+    /// It's always called at least once.
+    fn run(
+        day: u32,
+        phase: &'static str,
+        time: Duration,
+        mut f: impl FnMut() -> Res<()>,
+    ) -> Res<Bench> {
+        let mut samples = vec![];
+        let start = Instant::now();
+        while samples.is_empty() || (start.elapsed() < time && samples.len() < MAX_SAMPLES) {
+            let t = Instant::now();
+            f()?;
+            samples.push(t.elapsed());
+        }
+        Ok(Bench {
+            day,
+            phase,
+            samples,
+        })
+    }
+
+    fn median(&self) -> Duration {
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+        sorted[sorted.len() / 2]
+    }
+}
+
+/// One line per phase, e.g. `15 part2 11873000000`, with the median in nanoseconds.
+fn write_baseline(benches: &[Bench]) -> String {
+    let mut out = String::new();
+    for b in benches {
+        out += &format!("{} {} {}\n", b.day, b.phase, b.median().as_nanos());
+    }
+    out
+}
+
+fn read_baseline(text: &str) -> Res<HashMap<(u32, &'static str), Duration>> {
+    let mut medians = HashMap::new();
+    for (i, line) in text.lines().enumerate() {
+        let words: Vec<_> = line.split_whitespace().collect();
+        let (day, phase, nanos) = match words[..] {
+            [] => continue,
+            [day, phase, nanos] => (day, phase, nanos),
+            _ => {
+                return Err(
+                    format!("Baseline line {}: expected `<day> <phase> <nanos>`", i + 1).into(),
+                )
+            }
+        };
+        let phase = match phase {
+            "parse" => "parse",
+            "part1" => "part1",
+            "part2" => "part2",
+            _ => return Err(format!("Baseline line {}: unknown phase {}", i + 1, phase).into()),
+        };
+        // Nanoseconds are a whole number, so negative or fractional medians are rejected here.
+        let bad = |what| format!("Baseline line {}: expected {}", i + 1, what);
+        let day = day.parse().map_err(|_| bad("a day number"))?;
+        let nanos = nanos
+            .parse()
+            .map_err(|_| bad("a whole number of nanoseconds"))?;
+        medians.insert((day, phase), Duration::from_nanos(nanos));
+    }
+    Ok(medians)
+}
+
+impl Args {
+    fn parse() -> Res<Args> {
+        let mut args = Args {
+            days: vec![],
+            time: Duration::from_secs(1),
+            save: None,
+            baseline: None,
+        };
+
+        let mut iter = std::env::args().skip(1);
+        while let Some(arg) = iter.next() {
+            let mut value = || iter.next().ok_or(format!("Missing value for {}", arg));
+            match arg.as_str() {
+                // Cargo passes this to every benchmark.
+                "--bench" => (),
+                "--time" => {
+                    let secs: f64 = value()?
+                        .parse()
+                        .map_err(|_| "Expected a number of seconds")?;
+                    args.time = Duration::try_from_secs_f64(secs).map_err(|_| {
+                        format!("Expected a non-negative number of seconds, got {}", secs)
+                    })?;
+                }
+                "--save" => args.save = Some(PathBuf::from(value()?)),
+                "--baseline" => args.baseline = Some(PathBuf::from(value()?)),
+                days => {
+                    let (first, last) = days.split_once('-').unwrap_or((days, days));
+                    args.days.push((first.parse()?, last.parse()?));
+                }
+            }
+        }
+        Ok(args)
+    }
+
+    fn includes(&self, day: u32) -> bool {
+        self.days.is_empty()
+            || self
+                .days
+                .iter()
+                .any(|&(first, last)| (first..=last).contains(&day))
+    }
+}
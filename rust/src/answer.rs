@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+///
+/// Integers are stored as `Int` whenever they fit, so that equal answers always compare equal,
+/// no matter which integer type the solution happened to use.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Str(String),
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Answer {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(n),
+        }
+    }
+}
+
+/// Integer types that always fit.
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::from(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i32, u32, i64, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Str(String::from(s))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::BigInt(n) => write!(f, "{}", n),
+            Answer::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ints_normalize() {
+        assert_eq!(Answer::from(5_u32), Answer::from(5_i64));
+        assert_eq!(Answer::from(5_u64), Answer::Int(5));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Answer::from(554865447501099_u64).to_string(),
+            "554865447501099"
+        );
+        assert_eq!(Answer::from("vmhqr,qxfzc").to_string(), "vmhqr,qxfzc");
+    }
+}
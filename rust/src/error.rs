@@ -0,0 +1,274 @@
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+/// Something went wrong while reading or solving a puzzle.
+///
+/// Each variant carries a `Location`, which gets filled in as the error propagates outward:
+/// parsers know the line and column, and the registry knows the day.
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        loc: Location,
+        source: io::Error,
+    },
+
+    /// A line that doesn't have the expected shape.
+    MalformedLine {
+        loc: Location,
+        msg: String,
+    },
+
+    /// The input ended early.
+    UnexpectedEof {
+        loc: Location,
+        expected: String,
+    },
+
+    InvalidChar {
+        loc: Location,
+        c: char,
+    },
+
+    /// Input that's well-formed, but doesn't make sense, e.g. a key that's defined twice.
+    Invalid {
+        loc: Location,
+        msg: String,
+    },
+}
+
+/// Where an error occurred. Lines and columns count from 1; columns count chars, not bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub day: Option<u32>,
+    pub line: Option<usize>,
+    pub col: Option<usize>,
+}
+
+impl Error {
+    pub fn malformed(msg: impl Into<String>) -> Error {
+        Error::MalformedLine {
+            loc: Location::default(),
+            msg: msg.into(),
+        }
+    }
+
+    pub fn eof(expected: impl Into<String>) -> Error {
+        Error::UnexpectedEof {
+            loc: Location::default(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn invalid_char(c: char) -> Error {
+        Error::InvalidChar {
+            loc: Location::default(),
+            c,
+        }
+    }
+
+    pub fn invalid(msg: impl Into<String>) -> Error {
+        Error::Invalid {
+            loc: Location::default(),
+            msg: msg.into(),
+        }
+    }
+
+    pub fn location(&self) -> Location {
+        *self.loc()
+    }
+
+    fn loc(&self) -> &Location {
+        match self {
+            Error::Io { loc, .. }
+            | Error::MalformedLine { loc, .. }
+            | Error::UnexpectedEof { loc, .. }
+            | Error::InvalidChar { loc, .. }
+            | Error::Invalid { loc, .. } => loc,
+        }
+    }
+
+    fn loc_mut(&mut self) -> &mut Location {
+        match self {
+            Error::Io { loc, .. }
+            | Error::MalformedLine { loc, .. }
+            | Error::UnexpectedEof { loc, .. }
+            | Error::InvalidChar { loc, .. }
+            | Error::Invalid { loc, .. } => loc,
+        }
+    }
+
+    /// Set the day, unless it's already known.
+    pub fn in_day(mut self, day: u32) -> Error {
+        self.loc_mut().day.get_or_insert(day);
+        self
+    }
+
+    /// Set the line number, unless it's already known.
+    pub fn at_line(mut self, line: usize) -> Error {
+        self.loc_mut().line.get_or_insert(line);
+        self
+    }
+
+    /// Set the line and column, unless they're already known.
+    pub fn at(mut self, line: usize, col: usize) -> Error {
+        let loc = self.loc_mut();
+        if loc.line.is_none() {
+            loc.line = Some(line);
+            loc.col = Some(col);
+        }
+        self
+    }
+
+    /// Set the column, unless it's already known. For parsers that only see one line.
+    pub fn at_col(mut self, col: usize) -> Error {
+        self.loc_mut().col.get_or_insert(col);
+        self
+    }
+}
+
+/// The column of the character that starts at `byte_idx` in `line`. Columns count chars, not
+/// bytes, so that they match what an editor shows.
+pub fn col_of(line: &str, byte_idx: usize) -> usize {
+    line[..byte_idx].chars().count() + 1
+}
+
+/// Attach a location to the error in a `Result`, converting it into an `Error` if needed.
+pub trait Context<T> {
+    fn in_day(self, day: u32) -> Result<T, Error>;
+    fn at_line(self, line: usize) -> Result<T, Error>;
+    fn at(self, line: usize, col: usize) -> Result<T, Error>;
+    fn at_col(self, col: usize) -> Result<T, Error>;
+}
+
+impl<T, E: Into<Error>> Context<T> for Result<T, E> {
+    fn in_day(self, day: u32) -> Result<T, Error> {
+        self.map_err(|e| e.into().in_day(day))
+    }
+
+    fn at_line(self, line: usize) -> Result<T, Error> {
+        self.map_err(|e| e.into().at_line(line))
+    }
+
+    fn at(self, line: usize, col: usize) -> Result<T, Error> {
+        self.map_err(|e| e.into().at(line, col))
+    }
+
+    fn at_col(self, col: usize) -> Result<T, Error> {
+        self.map_err(|e| e.into().at_col(col))
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Error {
+        Error::Io {
+            loc: Location::default(),
+            source,
+        }
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Error {
+        Error::malformed(format!("bad number: {}", e))
+    }
+}
+
+impl From<String> for Error {
+    fn from(msg: String) -> Error {
+        Error::invalid(msg)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(msg: &str) -> Error {
+        Error::invalid(msg)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let loc = self.loc();
+        if *loc != Location::default() {
+            write!(f, "{}: ", loc)?;
+        }
+
+        match self {
+            Error::Io { source, .. } => write!(f, "{}", source),
+            Error::MalformedLine { msg, .. } => write!(f, "malformed line: {}", msg),
+            Error::UnexpectedEof { expected, .. } => {
+                write!(f, "unexpected end of input, expected {}", expected)
+            }
+            Error::InvalidChar { c, .. } => write!(f, "invalid character {:?}", c),
+            Error::Invalid { msg, .. } => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// E.g. `day 11, line 3, column 7`.
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(day) = self.day {
+            parts.push(format!("day {}", day));
+        }
+        if let Some(line) = self.line {
+            parts.push(format!("line {}", line));
+        }
+        if let Some(col) = self.col {
+            parts.push(format!("column {}", col));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let e = Error::invalid_char('x').at(3, 7).in_day(11);
+        assert_eq!(
+            e.to_string(),
+            "day 11, line 3, column 7: invalid character 'x'"
+        );
+
+        let e = Error::from("No solution");
+        assert_eq!(e.to_string(), "No solution");
+    }
+
+    #[test]
+    fn innermost_location_wins() {
+        let e = Error::malformed("oops").at_col(4).at_line(2).at(5, 1);
+        let loc = Location {
+            day: None,
+            line: Some(2),
+            col: Some(4),
+        };
+        assert_eq!(e.location(), loc);
+    }
+
+    #[test]
+    fn columns() {
+        assert_eq!(col_of("abc", 0), 1);
+        assert_eq!(col_of("café bags", 6), 6);
+    }
+
+    #[test]
+    fn context() {
+        let res: Result<i32, _> = "abc".parse::<i32>().at_line(9);
+        let e = res.unwrap_err();
+        assert!(matches!(e, Error::MalformedLine { .. }));
+        assert_eq!(e.location().line, Some(9));
+    }
+}
@@ -0,0 +1,95 @@
+//! Bus schedules with a planted earliest timestamp.
+
+use super::{Generated, Rng};
+use crate::{Answer, Answers};
+
+/// A schedule with `num_buses` buses, whose IDs are distinct primes. At most 10 buses.
+///
+/// For part 2, a timestamp is picked first, and then each bus is placed at an offset that
+/// departs in step with it.
+pub fn generate(rng: &mut Rng, num_buses: usize) -> Generated {
+    assert!((1..=10).contains(&num_buses));
+
+    // Keep the product small enough that the timestamp, plus an offset, fits in a u64. Each pick
+    // leaves room for the rest to be at least as big as it.
+    let mut ids: Vec<u64> = vec![];
+    let mut product: u64 = 1;
+    while ids.len() < num_buses {
+        let remaining = (num_buses - ids.len()) as f64;
+        let max = ((1_u64 << 62) as f64 / product as f64)
+            .powf(1.0 / remaining)
+            .min(1000.0);
+        let primes: Vec<_> = primes_below(max as u64 + 1)
+            .into_iter()
+            .filter(|p| !ids.contains(p))
+            .collect();
+        let p = *rng.choose(&primes);
+        ids.push(p);
+        product *= p;
+    }
+
+    let timestamp = rng.below(product);
+
+    // Bus p needs (timestamp + offset) % p == 0. Any offset that works modulo p will do, so bump
+    // it by multiples of p until it's not taken.
+    let mut offsets = vec![];
+    for &p in &ids {
+        let mut offset = (p - timestamp % p) % p;
+        while offsets.contains(&offset) {
+            offset += p;
+        }
+        offsets.push(offset);
+    }
+
+    let width = *offsets.iter().max().unwrap() as usize + 1;
+    let mut schedule = vec![String::from("x"); width];
+    for (&p, &offset) in ids.iter().zip(&offsets) {
+        schedule[offset as usize] = p.to_string();
+    }
+
+    // Avoid a bus departing exactly at the start time, since then the wait is 0.
+    let start_time = loop {
+        let t = rng.between(1000, 1_000_000);
+        if ids.iter().all(|&p| t % p != 0) {
+            break t;
+        }
+    };
+
+    // The first bus to depart after the start time, by brute force.
+    let part1 = (start_time..)
+        .find_map(|t| {
+            let bus = ids.iter().filter(|&&p| t % p == 0).min()?;
+            Some(bus * (t - start_time))
+        })
+        .unwrap();
+
+    Generated {
+        input: format!("{}\n{}\n", start_time, schedule.join(",")),
+        answers: Answers {
+            part1: Answer::from(part1),
+            part2: Some(Answer::from(timestamp)),
+        },
+    }
+}
+
+fn primes_below(n: u64) -> Vec<u64> {
+    (2..n)
+        .filter(|&p| (2..p).take_while(|d| d * d <= p).all(|d| p % d != 0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solver_agrees() {
+        for seed in 0..50 {
+            let mut rng = Rng::new(seed);
+            let num_buses = rng.between(1, 9) as usize;
+            let gen = generate(&mut rng, num_buses);
+            let answers = crate::solution(13, &mut gen.input.as_bytes()).unwrap();
+            assert_eq!(answers, gen.answers, "seed {}:\n{}", seed, gen.input);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Ticket notes with a planted field order.
+
+use super::{Generated, Rng};
+use crate::{Answer, Answers};
+use std::fmt::Write;
+
+/// Notes with `num_fields` fields and `num_tickets` valid nearby tickets, plus some invalid ones.
+///
+/// The solver narrows down the field order by elimination: some field fits only one column, the
+/// next fits that column and one more, and so on. So that's how the fields are planted. If the
+/// fields are numbered in elimination order, field k accepts 10 up to `10 * (n - k + 1) + 10`,
+/// and some ticket's value in field k's column is too big for field k + 1.
+///
+/// Every field rejects 5, and rejects anything above field 0's limit, so those are the
+/// invalid values.
+pub fn generate(rng: &mut Rng, num_fields: usize, num_tickets: usize) -> Generated {
+    assert!(num_fields >= 1 && num_tickets >= 1);
+    let n = num_fields;
+
+    // Indexed by elimination order.
+    let limit = |k: usize| 10 * (n - k) as u64 + 20;
+    let mut names: Vec<_> = (0..n).map(|k| format!("field {}", k)).collect();
+    let num_departures = rng.between(1, n.min(6) as u64) as usize;
+    for name in names.iter_mut().take(num_departures) {
+        *name = format!("departure {}", name);
+    }
+    rng.shuffle(&mut names);
+
+    // The column each field ends up in.
+    let mut columns: Vec<_> = (0..n).collect();
+    rng.shuffle(&mut columns);
+
+    let random_ticket = |rng: &mut Rng| {
+        let mut ticket = vec![0; n];
+        for k in 0..n {
+            ticket[columns[k]] = rng.between(10, limit(k));
+        }
+        ticket
+    };
+
+    let my_ticket = random_ticket(rng);
+    let mut tickets: Vec<_> = (0..num_tickets).map(|_| random_ticket(rng)).collect();
+
+    // Rule out field k + 1 from field k's column.
+    for k in 0..n {
+        let t = rng.below(num_tickets as u64) as usize;
+        let lo = if k + 1 < n { limit(k + 1) + 1 } else { 10 };
+        tickets[t][columns[k]] = rng.between(lo, limit(k));
+    }
+
+    // Some tickets get a single value that no field accepts.
+    let mut error_rate = 0;
+    for _ in 0..rng.between(0, num_tickets as u64 / 4 + 1) {
+        let mut ticket = random_ticket(rng);
+        let bad = if rng.chance(0.5) {
+            5
+        } else {
+            rng.between(limit(0) + 1, 999)
+        };
+        ticket[rng.below(n as u64) as usize] = bad;
+        error_rate += bad;
+        tickets.push(ticket);
+    }
+    rng.shuffle(&mut tickets);
+
+    let part2: u64 = (0..n)
+        .filter(|&k| names[k].starts_with("departure "))
+        .map(|k| my_ticket[columns[k]])
+        .product();
+
+    let mut input = String::new();
+    for (k, name) in names.iter().enumerate() {
+        let a = rng.between(1, 4);
+        let b = rng.between(6, 10);
+        writeln!(input, "{}: 1-{} or {}-{}", name, a, b, limit(k)).unwrap();
+    }
+    writeln!(
+        input,
+        "\nyour ticket:\n{}\n\nnearby tickets:",
+        join(&my_ticket)
+    )
+    .unwrap();
+    for t in &tickets {
+        writeln!(input, "{}", join(t)).unwrap();
+    }
+
+    Generated {
+        input,
+        answers: Answers {
+            part1: Answer::from(error_rate),
+            part2: Some(Answer::from(part2)),
+        },
+    }
+}
+
+fn join(ticket: &[u64]) -> String {
+    let vals: Vec<_> = ticket.iter().map(u64::to_string).collect();
+    vals.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solver_agrees() {
+        for seed in 0..50 {
+            let mut rng = Rng::new(seed);
+            let num_fields = rng.between(1, 25) as usize;
+            let num_tickets = rng.between(1, 50) as usize;
+            let gen = generate(&mut rng, num_fields, num_tickets);
+            let answers = crate::solution(16, &mut gen.input.as_bytes()).unwrap();
+            assert_eq!(answers, gen.answers, "seed {}:\n{}", seed, gen.input);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Jigsaw tiles cut from a random image, with sea monsters planted in it.
+
+use super::{Generated, Rng};
+use crate::{Answer, Answers};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+type Grid = Vec<Vec<bool>>;
+
+/// The pixels of a sea monster, relative to its top-left corner.
+const MONSTER: [(usize, usize); 15] = [
+    (1, 0),
+    (2, 1),
+    (2, 4),
+    (1, 5),
+    (1, 6),
+    (2, 7),
+    (2, 10),
+    (1, 11),
+    (1, 12),
+    (2, 13),
+    (2, 16),
+    (1, 17),
+    (1, 18),
+    (0, 18),
+    (1, 19),
+];
+const MONSTER_HEIGHT: usize = 3;
+const MONSTER_WIDTH: usize = 20;
+
+/// How much of the image is rough water, not counting the monsters.
+const DENSITY: f64 = 0.2;
+
+/// A `grid_dim` x `grid_dim` puzzle of tiles with `tile_dim` pixels on a side, with `num_monsters`
+/// sea monsters in the image. Tiles can be at most 64 pixels on a side.
+///
+/// Neighbouring tiles share their border, so the image is what's left after stripping the borders.
+/// Every border is unique, even when flipped, and isn't a palindrome, so there's only one way to
+/// fit the tiles together. Each tile is then randomly rotated and flipped.
+pub fn generate(rng: &mut Rng, grid_dim: usize, tile_dim: usize, num_monsters: usize) -> Generated {
+    assert!(grid_dim >= 2 && (3..=64).contains(&tile_dim));
+    let image_dim = grid_dim * (tile_dim - 2);
+    assert!(
+        image_dim >= MONSTER_WIDTH,
+        "Image is too small for a sea monster"
+    );
+
+    // Monsters go in non-overlapping slots, so that they're all counted.
+    let mut slots = vec![];
+    for i in 0..image_dim / MONSTER_HEIGHT {
+        for j in 0..image_dim / MONSTER_WIDTH {
+            slots.push((i * MONSTER_HEIGHT, j * MONSTER_WIDTH));
+        }
+    }
+    assert!(
+        (1..=slots.len()).contains(&num_monsters),
+        "Can't fit {} sea monsters",
+        num_monsters
+    );
+
+    // Re-roll the water until there are no sea monsters besides the planted ones, in any
+    // orientation. Otherwise there'd be more than one right answer.
+    let image = loop {
+        let mut image = vec![vec![false; image_dim]; image_dim];
+        for px in image.iter_mut().flatten() {
+            *px = rng.chance(DENSITY);
+        }
+
+        rng.shuffle(&mut slots);
+        for &(i, j) in &slots[..num_monsters] {
+            for &(di, dj) in &MONSTER {
+                image[i + di][j + dj] = true;
+            }
+        }
+
+        let counts: Vec<_> = orientations(&image).iter().map(count_monsters).collect();
+        if counts[0] == num_monsters && counts[1..].iter().all(|&n| n == 0) {
+            break image;
+        }
+    };
+
+    let roughness = image.iter().flatten().filter(|&&px| px).count() - MONSTER.len() * num_monsters;
+
+    let tiles = cut_tiles(rng, &image, grid_dim, tile_dim);
+
+    // Random ids, in random order.
+    let mut ids = HashSet::new();
+    while ids.len() < grid_dim * grid_dim {
+        ids.insert(rng.between(1000, 9999));
+    }
+    let mut ids: Vec<_> = ids.into_iter().collect();
+    ids.sort_unstable();
+    rng.shuffle(&mut ids);
+
+    let last = grid_dim - 1;
+    let corners: u64 = [(0, 0), (0, last), (last, 0), (last, last)]
+        .iter()
+        .map(|&(i, j)| ids[i * grid_dim + j])
+        .product();
+
+    let mut order: Vec<_> = (0..tiles.len()).collect();
+    rng.shuffle(&mut order);
+
+    let mut input = String::new();
+    for k in order {
+        let mut tile = tiles[k].clone();
+        for _ in 0..rng.below(4) {
+            tile = rotate(&tile);
+        }
+        if rng.chance(0.5) {
+            tile = flip(&tile);
+        }
+
+        writeln!(input, "Tile {}:", ids[k]).unwrap();
+        for row in &tile {
+            let row: String = row.iter().map(|&px| if px { '#' } else { '.' }).collect();
+            writeln!(input, "{}", row).unwrap();
+        }
+        writeln!(input).unwrap();
+    }
+
+    Generated {
+        input,
+        answers: Answers {
+            part1: Answer::from(corners),
+            part2: Some(Answer::from(roughness as u64)),
+        },
+    }
+}
+
+/// Cut the image into tiles, in row-major order, making up the borders.
+fn cut_tiles(rng: &mut Rng, image: &Grid, grid_dim: usize, tile_dim: usize) -> Vec<Grid> {
+    let d = tile_dim;
+
+    // Each border is `d` pixels long, and normalized by taking the lesser of it and its reverse,
+    // there are (2^d - 2^ceil(d/2)) / 2 non-palindromes to choose from.
+    let num_borders = 2 * grid_dim * (grid_dim + 1);
+    let available = (1_u128 << d) - (1 << ((d + 1) / 2));
+    assert!(
+        available / 2 >= 2 * num_borders as u128,
+        "Tiles are too small for unique borders"
+    );
+
+    // Borders share their end points, at the corners of the tiles.
+    let corners: Grid = (0..=grid_dim)
+        .map(|_| (0..=grid_dim).map(|_| rng.chance(0.5)).collect())
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut border = |rng: &mut Rng, first: bool, last: bool| -> Vec<bool> {
+        loop {
+            let mut b = vec![first];
+            b.extend((0..d - 2).map(|_| rng.chance(0.5)));
+            b.push(last);
+
+            let rev: Vec<_> = b.iter().rev().copied().collect();
+            if b != rev && seen.insert(b.clone().min(rev)) {
+                return b;
+            }
+        }
+    };
+
+    // horiz[i][j] is the top of tile (i, j), left to right; vert[i][j] is its left, top to bottom.
+    let horiz: Vec<Vec<_>> = (0..=grid_dim)
+        .map(|i| {
+            (0..grid_dim)
+                .map(|j| border(rng, corners[i][j], corners[i][j + 1]))
+                .collect()
+        })
+        .collect();
+    let vert: Vec<Vec<_>> = (0..grid_dim)
+        .map(|i| {
+            (0..=grid_dim)
+                .map(|j| border(rng, corners[i][j], corners[i + 1][j]))
+                .collect()
+        })
+        .collect();
+
+    let mut tiles = vec![];
+    for i in 0..grid_dim {
+        for j in 0..grid_dim {
+            let mut tile = vec![vec![false; d]; d];
+            for k in 1..d - 1 {
+                for l in 1..d - 1 {
+                    tile[k][l] = image[i * (d - 2) + k - 1][j * (d - 2) + l - 1];
+                }
+            }
+            for k in 0..d {
+                tile[0][k] = horiz[i][j][k];
+                tile[d - 1][k] = horiz[i + 1][j][k];
+                tile[k][0] = vert[i][j][k];
+                tile[k][d - 1] = vert[i][j + 1][k];
+            }
+            tiles.push(tile);
+        }
+    }
+    tiles
+}
+
+/// All 8 orientations, starting with the original.
+fn orientations(grid: &Grid) -> Vec<Grid> {
+    let mut ret = vec![grid.clone()];
+    for _ in 0..3 {
+        ret.push(rotate(ret.last().unwrap()));
+    }
+    let flipped: Vec<_> = ret.iter().map(flip).collect();
+    ret.extend(flipped);
+    ret
+}
+
+/// Clockwise.
+fn rotate(grid: &Grid) -> Grid {
+    let n = grid.len();
+    (0..n)
+        .map(|i| (0..n).map(|j| grid[n - 1 - j][i]).collect())
+        .collect()
+}
+
+/// Left to right.
+fn flip(grid: &Grid) -> Grid {
+    grid.iter()
+        .map(|row| row.iter().rev().copied().collect())
+        .collect()
+}
+
+/// Counts overlapping sea monsters, too.
+fn count_monsters(image: &Grid) -> usize {
+    let n = image.len();
+    let mut count = 0;
+    for i in 0..=n - MONSTER_HEIGHT {
+        for j in 0..=n - MONSTER_WIDTH {
+            if MONSTER.iter().all(|&(di, dj)| image[i + di][j + dj]) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solver_agrees() {
+        for seed in 0..20 {
+            let mut rng = Rng::new(seed);
+            let grid_dim = rng.between(3, 6) as usize;
+            let min_tile_dim = MONSTER_WIDTH.div_ceil(grid_dim) + 2;
+            let tile_dim = rng.between(min_tile_dim.max(10) as u64, 12) as usize;
+            let gen = generate(&mut rng, grid_dim, tile_dim, 1 + seed as usize % 3);
+            let answers = crate::solution(20, &mut gen.input.as_bytes()).unwrap();
+            assert_eq!(answers, gen.answers, "seed {}:\n{}", seed, gen.input);
+        }
+    }
+}
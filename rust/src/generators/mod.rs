@@ -0,0 +1,25 @@
+//! Random puzzle inputs with known answers, for property testing and for scaling beyond the
+//! official input sizes.
+//!
+//! Each generator builds its input from the answer outward, e.g. by planting the field order in
+//! day 16's tickets, so the answers don't depend on the solvers being tested.
+//!
+//! Only days 13, 16, and 20 have generators: they're the ones whose solvers rely on structure
+//! that a single input barely exercises (coprime bus IDs, a unique field order, and a jigsaw
+//! with unique borders). The other days are covered by their examples and real inputs.
+
+pub mod day13;
+pub mod day16;
+pub mod day20;
+mod rng;
+
+pub use rng::Rng;
+
+use crate::Answers;
+
+/// A random puzzle input, along with its answers.
+#[derive(Debug, Clone)]
+pub struct Generated {
+    pub input: String,
+    pub answers: Answers,
+}
@@ -0,0 +1,187 @@
+use crate::manifest::{Manifest, Verdict};
+use crate::runner::Report;
+use crate::Answer;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Render reports as a JSON array, with one object per day.
+///
+/// E.g.:
+/// ```text
+/// [
+///   {"day": 1, "input": "inputs/1", "error": null, "parse_ms": 0.035, "total_ms": 0.455, "parts": [
+///     {"part": 1, "answer": 731731, "error": null, "time_ms": 0.023},
+///     {"part": 2, "answer": 116115990, "error": null, "time_ms": 0.397}
+///   ]}
+/// ]
+/// ```
+///
+/// If there's a manifest, each part also gets an `expected` answer and a `verdict`.
+pub fn reports(reports: &[Report], manifest: Option<&Manifest>) -> String {
+    let objects: Vec<_> = reports.iter().map(|r| report(r, manifest)).collect();
+    if objects.is_empty() {
+        return String::from("[]");
+    }
+    format!("[\n  {}\n]", objects.join(",\n  "))
+}
+
+/// Helper for `reports`.
+fn report(r: &Report, manifest: Option<&Manifest>) -> String {
+    let input = match &r.input {
+        Some(path) => string(&path.display().to_string()),
+        None => String::from("null"),
+    };
+    let error = match &r.parts {
+        Ok(_) => String::from("null"),
+        Err(e) => string(&e.to_string()),
+    };
+
+    let mut parts = vec![];
+    if let Ok(ps) = &r.parts {
+        let verdicts = manifest.map(|m| m.check(r));
+
+        for (i, p) in ps.iter().enumerate() {
+            let (answer, error) = match &p.answer {
+                Ok(ans) => (answer(ans), String::from("null")),
+                Err(e) => (String::from("null"), string(&e.to_string())),
+            };
+
+            let mut obj = format!(
+                "{{\"part\": {}, \"answer\": {}, \"error\": {}, \"time_ms\": {}",
+                i + 1,
+                answer,
+                error,
+                millis(p.time)
+            );
+            if let (Some(m), Some(verdicts)) = (manifest, &verdicts) {
+                let expected = match m.expected(r.day, i + 1) {
+                    Some(s) => string(s),
+                    None => String::from("null"),
+                };
+                write!(
+                    obj,
+                    ", \"expected\": {}, \"verdict\": {}",
+                    expected,
+                    string(verdict(&verdicts[i]))
+                )
+                .unwrap();
+            }
+            obj.push('}');
+            parts.push(obj);
+        }
+    }
+
+    let parts = if parts.is_empty() {
+        String::from("[]")
+    } else {
+        format!("[\n    {}\n  ]", parts.join(",\n    "))
+    };
+
+    format!(
+        "{{\"day\": {}, \"input\": {}, \"error\": {}, \"parse_ms\": {}, \"total_ms\": {}, \"parts\": {}}}",
+        r.day,
+        input,
+        error,
+        millis(r.parse_time),
+        millis(r.total_time()),
+        parts
+    )
+}
+
+/// Integers are numbers, unless they're too big for an f64 to hold exactly, which is how most
+/// JSON parsers read numbers. Those, and everything else, are strings.
+fn answer(ans: &Answer) -> String {
+    match ans {
+        Answer::Int(n) if n.unsigned_abs() <= MAX_EXACT_F64 => n.to_string(),
+        Answer::Int(n) => string(&n.to_string()),
+        Answer::BigInt(n) => string(&n.to_string()),
+        Answer::Str(s) => string(s),
+    }
+}
+
+/// 2^53. Every integer up to this size fits in an f64.
+const MAX_EXACT_F64: u64 = 1 << 53;
+
+fn verdict(v: &Verdict) -> &'static str {
+    match v {
+        Verdict::Pass => "pass",
+        Verdict::Fail { .. } => "fail",
+        Verdict::Error(_) => "error",
+        Verdict::Unchecked => "unchecked",
+    }
+}
+
+fn millis(d: Duration) -> String {
+    format!("{:.3}", d.as_secs_f64() * 1e3)
+}
+
+/// A quoted and escaped JSON string.
+fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::PartReport;
+    use crate::Res;
+    use std::path::PathBuf;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+
+    #[test]
+    fn big_answers_are_strings() {
+        assert_eq!(answer(&Answer::Int(1 << 53)), "9007199254740992");
+        assert_eq!(answer(&Answer::Int(-(1 << 53))), "-9007199254740992");
+        assert_eq!(answer(&Answer::Int((1 << 53) + 1)), r#""9007199254740993""#);
+        assert_eq!(
+            answer(&Answer::BigInt(1 << 100)),
+            r#""1267650600228229401496703205376""#
+        );
+    }
+
+    #[test]
+    fn report_with_errors() -> Res<()> {
+        let r = Report {
+            day: 21,
+            input: Some(PathBuf::from("inputs/21")),
+            parse_time: Duration::from_micros(1500),
+            parts: Ok(vec![
+                PartReport {
+                    answer: Ok(Answer::from("mxmxvkd,sqjhc")),
+                    time: Duration::ZERO,
+                },
+                PartReport {
+                    answer: Err("Failed to solve".into()),
+                    time: Duration::ZERO,
+                },
+            ]),
+        };
+        let manifest = Manifest::read("21 5 mxmxvkd,sqjhc,fvjkl".as_bytes())?;
+
+        let expected = "[\n  \
+            {\"day\": 21, \"input\": \"inputs/21\", \"error\": null, \"parse_ms\": 1.500, \"total_ms\": 1.500, \"parts\": [\n    \
+            {\"part\": 1, \"answer\": \"mxmxvkd,sqjhc\", \"error\": null, \"time_ms\": 0.000, \"expected\": \"5\", \"verdict\": \"fail\"},\n    \
+            {\"part\": 2, \"answer\": null, \"error\": \"Failed to solve\", \"time_ms\": 0.000, \"expected\": \"mxmxvkd,sqjhc,fvjkl\", \"verdict\": \"error\"}\n  \
+            ]}\n]";
+        assert_eq!(reports(&[r], Some(&manifest)), expected);
+        Ok(())
+    }
+}
@@ -1,39 +1,69 @@
-use std::error::Error;
-
-mod solutions;
-
-pub type Res<T> = Result<T, Box<dyn Error>>;
-
-pub fn solution(day: u32) -> Res<()> {
-    assert!(1 <= day && day <= 25);
-
-    use solutions::*;
-    match day {
-        1 => day1::main(),
-        2 => day2::main(),
-        3 => day3::main(),
-        4 => day4::main(),
-        5 => day5::main(),
-        6 => day6::main(),
-        //7 => day7::main(),
-        //8 => day8::main(),
-        //9 => day9::main(),
-        //10 => day10::main(),
-        11 => day11::main(),
-        12 => day12::main(),
-        13 => day13::main(),
-        14 => day14::main(),
-        15 => day15::main(),
-        16 => day16::main(),
-        17 => day17::main(),
-        18 => day18::main(),
-        19 => day19::main(),
-        20 => day20::main(),
-        21 => day21::main(),
-        22 => day22::main(),
-        23 => day23::main(),
-        24 => day24::main(),
-        25 => day25::main(),
-        _ => Err(format!("Not yet implemented: day {}", day).into()),
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+mod answer;
+mod error;
+pub mod generators;
+pub mod json;
+pub mod manifest;
+pub mod python;
+pub mod runner;
+pub mod solutions;
+
+pub use answer::Answer;
+pub use error::{col_of, Context, Error, Location};
+pub use solutions::{Day, Puzzle, Solution, DAYS};
+
+pub type Res<T> = Result<T, Error>;
+
+/// The answers to both parts of a day's puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Answer,
+
+    /// Day 25 has no part 2.
+    pub part2: Option<Answer>,
+}
+
+/// Solve both parts of a day's puzzle.
+pub fn solution(day: u32, input: &mut dyn BufRead) -> Res<Answers> {
+    let day = solutions::get(day)?;
+    let puzzle = day.parse(input)?;
+    let part1 = puzzle.part1()?;
+    let part2 = if puzzle.has_part2() {
+        Some(puzzle.part2()?)
+    } else {
+        None
+    };
+
+    Ok(Answers { part1, part2 })
+}
+
+/// The repository's `inputs` directory, regardless of the current working directory.
+pub fn inputs_dir() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "inputs"]
+        .iter()
+        .collect()
+}
+
+/// Where the checked-in input for this day lives.
+pub fn input_path(day: u32) -> PathBuf {
+    inputs_dir().join(day.to_string())
+}
+
+/// Open the checked-in input for this day.
+pub fn open_input(day: u32) -> Res<BufReader<File>> {
+    open_file(&input_path(day))
+}
+
+/// Open a file, mentioning its path in the error message if that fails.
+pub fn open_file(path: &Path) -> Res<BufReader<File>> {
+    match File::open(path) {
+        Ok(f) => Ok(BufReader::new(f)),
+        Err(e) => Err(Error::Io {
+            loc: Location::default(),
+            source: io::Error::new(e.kind(), format!("Couldn't open {}: {}", path.display(), e)),
+        }),
     }
 }
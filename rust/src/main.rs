@@ -1,8 +1,22 @@
-use aoc_2020::Res;
+use aoc_2020::manifest::Manifest;
+use aoc_2020::python;
+use aoc_2020::runner::{self, Report, Table};
+use aoc_2020::solutions::day11::frames::Format;
+use aoc_2020::{json, Error, Res};
 use std::env;
+use std::io::{self, prelude::*, IsTerminal};
+use std::path::{Path, PathBuf};
 use std::process;
 
-fn main() -> Res<()> {
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+/// `main`, but errors are printed with their location, rather than debug-formatted.
+fn run() -> Res<()> {
     let mut args = env::args();
     let prog_name = args.next().unwrap();
     let args: Vec<_> = args.collect();
@@ -12,29 +26,517 @@ fn main() -> Res<()> {
         process::exit(1)
     };
 
-    if args.len() != 1 {
-        eprintln!("Expected 1 argument, got {}\n", args.len());
-        usage();
+    let args = match Args::parse(&args) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("{}\n", msg);
+            usage()
+        }
+    };
+
+    let inputs_dir = args.inputs.clone().unwrap_or_else(aoc_2020::inputs_dir);
+
+    let manifest = if args.check {
+        Some(Manifest::load(&inputs_dir.join("answers"))?)
+    } else {
+        None
+    };
+
+    if args.json {
+        let reports = match args.days {
+            Days::One(day) if !args.check => vec![run_one_report(day, &args)?],
+            _ => {
+                let (first, last) = args.days.range();
+                run_days(&inputs_dir, first, last)?
+            }
+        };
+        println!("{}", json::reports(&reports, manifest.as_ref()));
+
+        let failed = reports.iter().any(Report::has_errors)
+            || reports
+                .iter()
+                .flat_map(|r| manifest.as_ref().map(|m| m.check(r)).unwrap_or_default())
+                .any(|v| !v.is_ok());
+        if failed {
+            process::exit(1);
+        }
+        return Ok(());
     }
 
-    let day: u32 = match args[0].parse() {
-        Ok(n) if 1 <= n && n <= 25 => n,
-        Ok(n) => {
-            eprintln!("Expected a number from 1 through 25, got {}\n", n);
-            usage()
+    if let Some(dir) = &args.frames {
+        return export_frames(dir, &args);
+    }
+
+    if let Some(dir) = &args.svg {
+        return export_svg(dir, &args);
+    }
+
+    if args.cross_check {
+        let (first, last) = args.days.range();
+        if !cross_check(&inputs_dir, &args, first, last) {
+            process::exit(1);
         }
-        Err(_) => {
-            eprintln!("Not a number from 1 through 25: {}\n", args[0]);
-            usage()
+        return Ok(());
+    }
+
+    if let Some(manifest) = manifest {
+        let (first, last) = args.days.range();
+        let reports = run_days(&inputs_dir, first, last)?;
+        if !check(&manifest, &reports) {
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
+    match args.days {
+        Days::One(day) => run_one(day, &args),
+        Days::Range(first, last) => {
+            let reports = run_days(&inputs_dir, first, last)?;
+            println!("{}", Table(&reports));
+            if reports.iter().any(Report::has_errors) {
+                process::exit(1);
+            }
+            Ok(())
         }
+    }
+}
+
+/// Print the answers, one per line.
+fn run_one(day: u32, args: &Args) -> Res<()> {
+    let (mut input, _path) = open_input(day, args)?;
+
+    let answers = aoc_2020::solution(day, &mut input)?;
+    println!("{}", answers.part1);
+    if let Some(ans) = answers.part2 {
+        println!("{}", ans);
+    }
+
+    Ok(())
+}
+
+/// Like `run_one`, but errors in solving end up in the report.
+fn run_one_report(day: u32, args: &Args) -> Res<Report> {
+    let day = aoc_2020::solutions::get(day)?;
+
+    let path = input_path(day.number, args);
+    let mut input: Box<dyn BufRead> = match &path {
+        Some(path) => match aoc_2020::open_file(path) {
+            Ok(input) => Box::new(input),
+            Err(e) => return Ok(Report::from_error(day.number, Some(path.clone()), e)),
+        },
+        None => Box::new(io::stdin().lock()),
     };
+    let mut report = runner::run(day, &mut input);
+    report.input = path;
+    Ok(report)
+}
+
+/// Run every implemented day in the range, reading each input from `dir`.
+///
+/// A day that fails doesn't stop the others; the error is in its report.
+fn run_days(dir: &Path, first: u32, last: u32) -> Res<Vec<Report>> {
+    let mut reports = vec![];
+
+    for day in aoc_2020::DAYS
+        .iter()
+        .filter(|d| (first..=last).contains(&d.number))
+    {
+        let path = dir.join(day.number.to_string());
+        let mut report = match aoc_2020::open_file(&path) {
+            Ok(mut input) => runner::run(day, &mut input),
+            Err(e) => Report::from_error(day.number, None, e),
+        };
+        report.input = Some(path);
+        reports.push(report);
+    }
+
+    Ok(reports)
+}
+
+/// Print a verdict for each part, and a summary at the end.
+///
+/// Returns false if anything failed.
+fn check(manifest: &Manifest, reports: &[Report]) -> bool {
+    let mut num_passed = 0;
+    let mut num_unchecked = 0;
+    let mut num_failed = 0;
+
+    for r in reports {
+        for (i, verdict) in manifest.check(r).iter().enumerate() {
+            println!("{:>2}.{}  {}", r.day, i + 1, verdict);
+
+            if !verdict.is_ok() {
+                num_failed += 1;
+            } else if manifest.expected(r.day, i + 1).is_some() {
+                num_passed += 1;
+            } else {
+                num_unchecked += 1;
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} unchecked",
+        num_passed, num_failed, num_unchecked
+    );
+    num_failed == 0
+}
+
+/// Compare each day that has a Python script against the Rust solution, printing a verdict for
+/// each, and a summary at the end.
+///
+/// Returns false if any day disagreed or failed.
+fn cross_check(dir: &Path, args: &Args, first: u32, last: u32) -> bool {
+    let mut num_agreed = 0;
+    let mut num_skipped = 0;
+    let mut num_failed = 0;
+
+    for day in (first..=last).filter(|&d| python::has_script(d)) {
+        let path = args
+            .input
+            .clone()
+            .unwrap_or_else(|| dir.join(day.to_string()));
+        match python::cross_check(day, &path) {
+            Ok(verdict) => {
+                println!("{:>2}  {}", day, verdict);
+                match verdict {
+                    python::Verdict::Agree => num_agreed += 1,
+                    python::Verdict::Skipped(_) => num_skipped += 1,
+                    python::Verdict::Disagree(_) => num_failed += 1,
+                }
+            }
+            Err(e) => {
+                println!("{:>2}  ERROR {}", day, e);
+                num_failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "\n{} agreed, {} failed, {} skipped",
+        num_agreed, num_failed, num_skipped
+    );
+    num_failed == 0
+}
+
+/// Write every generation of both parts of day 11 as frames, into a subdirectory of `dir` per
+/// part, and say how each part ended.
+fn export_frames(dir: &Path, args: &Args) -> Res<()> {
+    use aoc_2020::solutions::day11::{frames, rules::Rules, sim::Outcome, Day11};
+    use aoc_2020::Solution;
+
+    let (mut input, _path) = open_input(11, args)?;
+    let initial = Day11::parse(&mut input)?;
+
+    for (part, rules) in [(1, Rules::PART1), (2, Rules::PART2)] {
+        let part_dir = dir.join(format!("part{}", part));
+        let (outcome, num_frames) = frames::export(
+            &initial,
+            &rules,
+            frames::MAX_FRAMES,
+            args.frame_format,
+            &part_dir,
+        )?;
+        let ending = match outcome {
+            Outcome::Stable { steps, .. } => format!("stable after {} generations", steps),
+            Outcome::Cycle { start, period, .. } => {
+                format!("repeats every {} generations from {}", period, start)
+            }
+            Outcome::GaveUp { steps, .. } => format!("still changing after {} generations", steps),
+        };
+        println!(
+            "part {}: {}; {} frames in {}",
+            part,
+            ending,
+            num_frames,
+            part_dir.display()
+        );
+    }
 
-    aoc_2020::solution(day)
+    Ok(())
+}
+
+/// Draw the ship's path for both parts of day 12, as `part1.svg` and `part2.svg` in `dir`, and
+/// summarize each voyage.
+fn export_svg(dir: &Path, args: &Args) -> Res<()> {
+    use aoc_2020::solutions::day12::{trajectory::Trajectory, Day12, Mode};
+    use aoc_2020::Solution;
+    use std::fs::{self, File};
+    use std::io::BufWriter;
+
+    let (mut input, _path) = open_input(12, args)?;
+    let actions = Day12::parse(&mut input)?;
+    fs::create_dir_all(dir)?;
+
+    for (part, mode) in [(1, Mode::Heading), (2, Mode::Waypoint)] {
+        let trajectory = Trajectory::record(&actions, mode);
+        let path = dir.join(format!("part{}.svg", part));
+        let mut out = BufWriter::new(File::create(&path)?);
+        trajectory.write_svg(&mut out)?;
+        out.flush()?;
+
+        let bounds = trajectory.bounds();
+        let (farthest_step, farthest) = trajectory.max_distance();
+        println!(
+            "part {}: ends {} from the start, at most {} (step {}), within {}x{}; drawn in {}",
+            part,
+            trajectory.end().pos.manhattan_norm(),
+            farthest,
+            farthest_step,
+            bounds.width(),
+            bounds.height(),
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// The parsed command-line arguments.
+struct Args {
+    days: Days,
+
+    /// Explicit path given by `--input`.
+    input: Option<PathBuf>,
+
+    /// Input set directory given by `--inputs`.
+    inputs: Option<PathBuf>,
+
+    check: bool,
+
+    /// `--cross-check`: compare against the Python scripts.
+    cross_check: bool,
+
+    /// `--format json`
+    json: bool,
+
+    /// `--frames <dir>`: where to write day 11's frames.
+    frames: Option<PathBuf>,
+
+    frame_format: Format,
+
+    /// `--svg <dir>`: where to draw day 12's paths.
+    svg: Option<PathBuf>,
+}
+
+/// Which day(s) to run.
+enum Days {
+    One(u32),
+
+    /// Inclusive on both ends.
+    Range(u32, u32),
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Args, String> {
+        let mut days = None;
+        let mut input = None;
+        let mut inputs = None;
+        let mut check = false;
+        let mut cross_check = false;
+        let mut json = false;
+        let mut frames = None;
+        let mut frame_format = None;
+        let mut svg = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--input" {
+                match args.next() {
+                    Some(path) => input = Some(PathBuf::from(path)),
+                    None => return Err(String::from("Expected a path after --input")),
+                }
+            } else if arg == "--inputs" {
+                match args.next() {
+                    Some(path) => inputs = Some(PathBuf::from(path)),
+                    None => return Err(String::from("Expected a directory after --inputs")),
+                }
+            } else if arg == "--check" {
+                check = true;
+            } else if arg == "--cross-check" {
+                cross_check = true;
+            } else if arg == "--format" {
+                match args.next().map(String::as_str) {
+                    Some("text") => json = false,
+                    Some("json") => json = true,
+                    Some(other) => return Err(format!("Unknown format: {}", other)),
+                    None => return Err(String::from("Expected `text` or `json` after --format")),
+                }
+            } else if arg == "--frames" {
+                match args.next() {
+                    Some(dir) => frames = Some(PathBuf::from(dir)),
+                    None => return Err(String::from("Expected a directory after --frames")),
+                }
+            } else if arg == "--frame-format" {
+                match args.next() {
+                    Some(fmt) => {
+                        frame_format = Some(fmt.parse().map_err(|e: Error| e.to_string())?)
+                    }
+                    None => {
+                        return Err(String::from(
+                            "Expected `text`, `pgm`, or `ppm` after --frame-format",
+                        ))
+                    }
+                }
+            } else if arg == "--svg" {
+                match args.next() {
+                    Some(dir) => svg = Some(PathBuf::from(dir)),
+                    None => return Err(String::from("Expected a directory after --svg")),
+                }
+            } else if days.is_none() {
+                days = Some(Days::parse(arg)?);
+            } else {
+                return Err(format!("Unexpected argument: {}", arg));
+            }
+        }
+
+        let days = match days {
+            Some(days) => days,
+            None => {
+                return Err(String::from(
+                    "Expected a day number, a range of days, or `all`",
+                ))
+            }
+        };
+
+        if input.is_some() && (check || matches!(days, Days::Range(..))) {
+            return Err(String::from(
+                "--input only makes sense for a single day, without --check",
+            ));
+        }
+
+        if cross_check && (check || json) {
+            return Err(String::from(
+                "--cross-check can't be combined with --check or --format json",
+            ));
+        }
+
+        if frames.is_some() && (!matches!(days, Days::One(11)) || check || cross_check || json) {
+            return Err(String::from(
+                "--frames only works for day 11, without --check, --cross-check, or --format json",
+            ));
+        }
+
+        if frame_format.is_some() && frames.is_none() {
+            return Err(String::from(
+                "--frame-format only makes sense with --frames",
+            ));
+        }
+
+        if svg.is_some() && (!matches!(days, Days::One(12)) || check || cross_check || json) {
+            return Err(String::from(
+                "--svg only works for day 12, without --check, --cross-check, or --format json",
+            ));
+        }
+
+        Ok(Args {
+            days,
+            input,
+            inputs,
+            check,
+            cross_check,
+            json,
+            frames,
+            frame_format: frame_format.unwrap_or(Format::Text),
+            svg,
+        })
+    }
+}
+
+impl Days {
+    /// E.g., `7`, `12-18`, or `all`.
+    fn parse(arg: &str) -> Result<Days, String> {
+        if arg == "all" {
+            return Ok(Days::Range(1, 25));
+        }
+
+        let mut ends = arg.splitn(2, '-');
+        let first = parse_day(ends.next().unwrap())?;
+        match ends.next() {
+            None => Ok(Days::One(first)),
+            Some(last) => {
+                let last = parse_day(last)?;
+                if first <= last {
+                    Ok(Days::Range(first, last))
+                } else {
+                    Err(format!("Empty range of days: {}", arg))
+                }
+            }
+        }
+    }
+
+    fn range(&self) -> (u32, u32) {
+        match *self {
+            Days::One(day) => (day, day),
+            Days::Range(first, last) => (first, last),
+        }
+    }
+}
+
+fn parse_day(arg: &str) -> Result<u32, String> {
+    match arg.parse() {
+        Ok(n) if (1..=25).contains(&n) => Ok(n),
+        Ok(n) => Err(format!("Expected a number from 1 through 25, got {}", n)),
+        Err(_) => Err(format!("Not a number from 1 through 25: {}", arg)),
+    }
+}
+
+/// Read from the `--input` file if there is one, then the `--inputs` directory, then stdin.
+///
+/// If stdin is a terminal (i.e., nothing was piped in), fall back to the checked-in input.
+///
+/// Also returns the path of the input, or None for stdin.
+fn open_input(day: u32, args: &Args) -> Res<(Box<dyn BufRead>, Option<PathBuf>)> {
+    match input_path(day, args) {
+        Some(path) => {
+            let input = aoc_2020::open_file(&path)?;
+            Ok((Box::new(input), Some(path)))
+        }
+        None => Ok((Box::new(io::stdin().lock()), None)),
+    }
+}
+
+/// Where `open_input` reads from, or None for stdin.
+fn input_path(day: u32, args: &Args) -> Option<PathBuf> {
+    if let Some(path) = &args.input {
+        Some(path.clone())
+    } else if let Some(dir) = &args.inputs {
+        Some(dir.join(day.to_string()))
+    } else if io::stdin().is_terminal() {
+        Some(aoc_2020::input_path(day))
+    } else {
+        None
+    }
 }
 
 fn eprint_usage(prog_name: &str) {
     eprintln!(
-        "usage: `{} <num>`\nwhere <num> is a number from 1 through 25",
+        "usage: `{0} [options] <num>`\n\
+         \x20  or: `{0} [options] <first>-<last>`\n\
+         \x20  or: `{0} [options] all`\n\
+         where <num>, <first>, and <last> are numbers from 1 through 25\n\
+         \n\
+         For a single day, the answers are printed one per line.\n\
+         For a range of days, each implemented day is run, and a table of answers and timings\n\
+         is printed.\n\
+         \n\
+         options:\n\
+         \x20 --input <path>  Read a single day's input from <path>. Otherwise it's read from\n\
+         \x20                 stdin, unless stdin is a terminal.\n\
+         \x20 --inputs <dir>  Use the input set in <dir>, which has one file per day, named\n\
+         \x20                 by number. Defaults to the repository's `inputs` directory.\n\
+         \x20 --check         Compare each answer against the input set's `answers` file.\n\
+         \x20 --cross-check   Compare each answer against the Python script in the\n\
+         \x20                 repository's `python` directory, for the days that have one.\n\
+         \x20 --format <fmt>  `text` (the default) or `json`. JSON output has one object per\n\
+         \x20                 day, with answers, timings, input path, and any errors.\n\
+         \x20 --frames <dir>  For day 11, write every generation of each part into\n\
+         \x20                 <dir>/part1 and <dir>/part2, one file per generation.\n\
+         \x20                 Those directories must be empty, or not exist yet.\n\
+         \x20 --frame-format <fmt>\n\
+         \x20                 `text` (the default), or `pgm` or `ppm` images.\n\
+         \x20 --svg <dir>     For day 12, draw the ship's path for each part as\n\
+         \x20                 <dir>/part1.svg and <dir>/part2.svg.",
         prog_name
     );
 }
@@ -0,0 +1,208 @@
+use crate::runner::Report;
+use crate::{Answer, Error, Res};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::path::Path;
+
+/// The expected answers for one input set.
+///
+/// The file has one line per day: the day number, followed by the answer to each part, separated
+/// by whitespace. A `-` marks an answer that isn't known. Blank lines and `#` comments are ignored.
+///
+/// E.g.:
+/// ```text
+/// # day  part 1  part 2
+/// 1      731731  116115990
+/// 25     9714832
+/// ```
+pub struct Manifest {
+    days: HashMap<u32, Vec<Option<String>>>,
+}
+
+impl Manifest {
+    pub fn read(input: impl BufRead) -> Res<Manifest> {
+        let mut days = HashMap::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let day: u32 = match words.next().unwrap().parse() {
+                Ok(n) if (1..=25).contains(&n) => n,
+                _ => {
+                    let msg = format!("expected a day number: {}", line);
+                    return Err(Error::malformed(msg).at(i + 1, 1));
+                }
+            };
+
+            let answers: Vec<_> = words
+                .map(|w| {
+                    if w == "-" {
+                        None
+                    } else {
+                        Some(String::from(w))
+                    }
+                })
+                .collect();
+            if !(1..=2).contains(&answers.len()) {
+                let msg = format!("expected 1 or 2 answers: {}", line);
+                return Err(Error::malformed(msg).at_line(i + 1));
+            }
+
+            if days.insert(day, answers).is_some() {
+                let msg = format!("day {} listed twice", day);
+                return Err(Error::invalid(msg).at(i + 1, 1));
+            }
+        }
+
+        Ok(Manifest { days })
+    }
+
+    pub fn load(path: &Path) -> Res<Manifest> {
+        Manifest::read(crate::open_file(path)?)
+    }
+
+    /// The expected answer to this part (1 or 2) of this day, if it's known.
+    pub fn expected(&self, day: u32, part: usize) -> Option<&str> {
+        let answers = self.days.get(&day)?;
+        answers.get(part - 1)?.as_deref()
+    }
+
+    /// Compare each part of the report against the expected answers.
+    ///
+    /// Returns one verdict per part.
+    pub fn check(&self, report: &Report) -> Vec<Verdict> {
+        let parts = match &report.parts {
+            Ok(parts) => parts,
+            Err(e) => {
+                // Nothing ran, so every part is in error.
+                let num_parts = self.days.get(&report.day).map_or(1, Vec::len);
+                return vec![Verdict::Error(e.to_string()); num_parts];
+            }
+        };
+
+        let mut verdicts = vec![];
+        for (i, p) in parts.iter().enumerate() {
+            let verdict = match (&p.answer, self.expected(report.day, i + 1)) {
+                (Err(e), _) => Verdict::Error(e.to_string()),
+                (Ok(_), None) => Verdict::Unchecked,
+                (Ok(actual), Some(expected)) => {
+                    // Compare textually, since e.g. day 23's answer is a string of digits.
+                    if actual.to_string() == expected {
+                        Verdict::Pass
+                    } else {
+                        Verdict::Fail {
+                            expected: String::from(expected),
+                            actual: actual.clone(),
+                        }
+                    }
+                }
+            };
+            verdicts.push(verdict);
+        }
+        verdicts
+    }
+}
+
+/// The result of checking one part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Answer,
+    },
+    Error(String),
+
+    /// There's no expected answer in the manifest.
+    Unchecked,
+}
+
+impl Verdict {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Verdict::Pass | Verdict::Unchecked)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL  expected {}, got {}", expected, actual)
+            }
+            Verdict::Error(e) => write!(f, "ERROR {}", e),
+            Verdict::Unchecked => write!(f, "unchecked"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::PartReport;
+    use std::time::Duration;
+
+    fn report(answers: Vec<Res<Answer>>) -> Report {
+        let parts = answers
+            .into_iter()
+            .map(|answer| PartReport {
+                answer,
+                time: Duration::ZERO,
+            })
+            .collect();
+        Report {
+            day: 23,
+            input: None,
+            parse_time: Duration::ZERO,
+            parts: Ok(parts),
+        }
+    }
+
+    #[test]
+    fn read() -> Res<()> {
+        let text = "# comment\n\n1 731731 116115990\n23  82934675 -\n25 9714832\n";
+        let manifest = Manifest::read(text.as_bytes())?;
+
+        assert_eq!(manifest.expected(1, 2), Some("116115990"));
+        assert_eq!(manifest.expected(23, 1), Some("82934675"));
+        assert_eq!(manifest.expected(23, 2), None);
+        assert_eq!(manifest.expected(25, 2), None);
+        assert_eq!(manifest.expected(7, 1), None);
+        Ok(())
+    }
+
+    #[test]
+    fn read_errors() {
+        assert!(Manifest::read("26 1 2".as_bytes()).is_err());
+        assert!(Manifest::read("1 2 3 4".as_bytes()).is_err());
+
+        let e = Manifest::read("1 2\n1 2".as_bytes()).err().unwrap();
+        assert_eq!(e.location().line, Some(2));
+    }
+
+    #[test]
+    fn check() -> Res<()> {
+        let manifest = Manifest::read("23 82934675 474600314018".as_bytes())?;
+
+        let r = report(vec![Ok(Answer::from("82934675")), Ok(Answer::from(5_u64))]);
+        let expected = vec![
+            Verdict::Pass,
+            Verdict::Fail {
+                expected: String::from("474600314018"),
+                actual: Answer::Int(5),
+            },
+        ];
+        assert_eq!(manifest.check(&r), expected);
+
+        let r = report(vec![Err("oops".into()), Ok(Answer::from(474600314018_u64))]);
+        let expected = vec![Verdict::Error(String::from("oops")), Verdict::Pass];
+        assert_eq!(manifest.check(&r), expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+//! Cross-check the Rust solutions against the reference Python scripts in the repository's
+//! `python` directory.
+//!
+//! The scripts read `../inputs/N` relative to the working directory (or stdin, for day 23), and
+//! print one answer per line. So each script runs in a scratch directory laid out the same way,
+//! with a copy of the input.
+
+use crate::{Answers, Error, Res};
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// The repository's `python` directory, regardless of the current working directory.
+pub fn scripts_dir() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "python"]
+        .iter()
+        .collect()
+}
+
+pub fn script_path(day: u32) -> PathBuf {
+    scripts_dir().join(format!("{}.py", day))
+}
+
+/// Is there a Python solution for this day?
+pub fn has_script(day: u32) -> bool {
+    script_path(day).is_file()
+}
+
+/// The result of comparing one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// Both gave the same answers.
+    Agree,
+
+    /// One entry per part that differs. `None` if that side has no answer for the part.
+    Disagree(Vec<Mismatch>),
+
+    /// The comparison couldn't be made, e.g. because python3 or a module it imports is missing.
+    Skipped(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub part: usize,
+    pub rust: Option<String>,
+    pub python: Option<String>,
+}
+
+/// Solve `input` with both the Rust solution and the Python script, and compare the answers.
+///
+/// An error in the Rust solution is returned as an error. So is a Python script that fails
+/// for any reason other than a missing module.
+pub fn cross_check(day: u32, input: &Path) -> Res<Verdict> {
+    if !has_script(day) {
+        return Ok(Verdict::Skipped(format!("no {}.py", day)));
+    }
+
+    let rust = crate::solution(day, &mut crate::open_file(input)?)?;
+    let python = match run_script(day, input)? {
+        Ok(answers) => answers,
+        Err(reason) => return Ok(Verdict::Skipped(reason)),
+    };
+
+    Ok(compare(&rust, &python))
+}
+
+/// Run `N.py` on the input, and return the answers it prints.
+///
+/// The inner result is Err if the script couldn't run at all, with the reason.
+fn run_script(day: u32, input: &Path) -> Res<Result<Vec<String>, String>> {
+    let scratch = scratch_dir(day);
+    let result = run_in(&scratch, day, input);
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+/// Helper for `run_script`.
+fn run_in(scratch: &Path, day: u32, input: &Path) -> Res<Result<Vec<String>, String>> {
+    let work_dir = scratch.join("run");
+    let inputs_dir = scratch.join("inputs");
+    fs::create_dir_all(&work_dir)?;
+    fs::create_dir_all(&inputs_dir)?;
+    fs::copy(input, inputs_dir.join(day.to_string()))?;
+
+    let output = Command::new("python3")
+        .arg(script_path(day))
+        .current_dir(&work_dir)
+        .stdin(File::open(input)?)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output();
+    let output = match output {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Ok(Err(String::from("python3 not found")))
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        if let Some(line) = stderr
+            .lines()
+            .find(|l| l.starts_with("ModuleNotFoundError"))
+        {
+            return Ok(Err(line.to_string()));
+        }
+        return Err(Error::invalid(format!(
+            "{}.py failed ({}):\n{}",
+            day,
+            output.status,
+            stderr.trim_end()
+        )));
+    }
+
+    Ok(Ok(parse_output(&String::from_utf8_lossy(&output.stdout))))
+}
+
+/// A fresh directory for one script run. Includes the process id, so that concurrent runs don't
+/// collide.
+fn scratch_dir(day: u32) -> PathBuf {
+    std::env::temp_dir().join(format!("aoc-2020-python-{}-{}", std::process::id(), day))
+}
+
+/// The answer is the last word of each line. Some scripts print their working, too, e.g. day 23
+/// prints `a b a*b`.
+fn parse_output(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .map(String::from)
+        .collect()
+}
+
+fn compare(rust: &Answers, python: &[String]) -> Verdict {
+    let rust: Vec<_> = std::iter::once(&rust.part1)
+        .chain(&rust.part2)
+        .map(|ans| ans.to_string())
+        .collect();
+
+    let mut mismatches = vec![];
+    for i in 0..rust.len().max(python.len()) {
+        let r = rust.get(i);
+        let p = python.get(i);
+        if r != p {
+            mismatches.push(Mismatch {
+                part: i + 1,
+                rust: r.cloned(),
+                python: p.cloned(),
+            });
+        }
+    }
+
+    if mismatches.is_empty() {
+        Verdict::Agree
+    } else {
+        Verdict::Disagree(mismatches)
+    }
+}
+
+impl Verdict {
+    pub fn is_ok(&self) -> bool {
+        !matches!(self, Verdict::Disagree(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Agree => write!(f, "agree"),
+            Verdict::Disagree(mismatches) => {
+                write!(f, "DIFFER")?;
+                for m in mismatches {
+                    let show = |ans: &Option<String>| ans.clone().unwrap_or_else(|| "-".into());
+                    write!(
+                        f,
+                        "  part {}: rust {}, python {}",
+                        m.part,
+                        show(&m.rust),
+                        show(&m.python)
+                    )?;
+                }
+                Ok(())
+            }
+            Verdict::Skipped(reason) => write!(f, "skipped ({})", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+
+    #[test]
+    fn compare_output() {
+        let rust = Answers {
+            part1: Answer::from("54327968"),
+            part2: Some(Answer::from(157410423276_u64)),
+        };
+
+        let python = parse_output("54327968\n\n934001 168532 157410423276\n");
+        assert_eq!(compare(&rust, &python), Verdict::Agree);
+
+        let python = parse_output("54327968\n");
+        let expected = Verdict::Disagree(vec![Mismatch {
+            part: 2,
+            rust: Some(String::from("157410423276")),
+            python: None,
+        }]);
+        assert_eq!(compare(&rust, &python), expected);
+        assert_eq!(
+            expected.to_string(),
+            "DIFFER  part 2: rust 157410423276, python -"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{Answer, Day, Error, Res};
+use std::fmt;
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// The outcome of running a single day, along with how long each phase took.
+pub struct Report {
+    pub day: u32,
+
+    /// Where the input came from. None for stdin.
+    pub input: Option<PathBuf>,
+
+    pub parse_time: Duration,
+
+    /// One entry per part. Err if the input failed to parse.
+    pub parts: Res<Vec<PartReport>>,
+}
+
+pub struct PartReport {
+    pub answer: Res<Answer>,
+    pub time: Duration,
+}
+
+/// Parse the input and solve each part, timing each phase separately.
+///
+/// Errors are recorded in the report, rather than returned, so that one bad day doesn't stop the
+/// others from running.
+pub fn run(day: &Day, input: &mut dyn BufRead) -> Report {
+    let start = Instant::now();
+    let puzzle = day.parse(input);
+    let parse_time = start.elapsed();
+
+    let parts = puzzle.map(|puzzle| {
+        let mut parts = vec![];
+
+        let start = Instant::now();
+        let answer = puzzle.part1();
+        parts.push(PartReport {
+            answer,
+            time: start.elapsed(),
+        });
+
+        if puzzle.has_part2() {
+            let start = Instant::now();
+            let answer = puzzle.part2();
+            parts.push(PartReport {
+                answer,
+                time: start.elapsed(),
+            });
+        }
+
+        parts
+    });
+
+    Report {
+        day: day.number,
+        input: None,
+        parse_time,
+        parts,
+    }
+}
+
+impl Report {
+    /// A day that couldn't be run at all, e.g. because its input couldn't be opened.
+    pub fn from_error(day: u32, input: Option<PathBuf>, e: Error) -> Report {
+        Report {
+            day,
+            input,
+            parse_time: Duration::ZERO,
+            parts: Err(e),
+        }
+    }
+
+    /// Did parsing, or either part, fail?
+    pub fn has_errors(&self) -> bool {
+        match &self.parts {
+            Ok(parts) => parts.iter().any(|p| p.answer.is_err()),
+            Err(_) => true,
+        }
+    }
+
+    /// Parse time plus the time spent on each part.
+    pub fn total_time(&self) -> Duration {
+        let parts = match &self.parts {
+            Ok(parts) => parts.iter().map(|p| p.time).sum(),
+            Err(_) => Duration::ZERO,
+        };
+        self.parse_time + parts
+    }
+
+    pub fn part_time(&self, idx: usize) -> Option<Duration> {
+        let parts = self.parts.as_ref().ok()?;
+        parts.get(idx).map(|p| p.time)
+    }
+}
+
+/// A table of timings and answers, with one row per report, and a total at the bottom.
+pub struct Table<'a>(pub &'a [Report]);
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3}  {:>9}  {:>9}  {:>9}  {:>9}  Answers",
+            "Day", "Parse", "Part 1", "Part 2", "Total"
+        )?;
+
+        for r in self.0 {
+            let answers = match &r.parts {
+                Ok(parts) => {
+                    let answers: Vec<_> = parts
+                        .iter()
+                        .map(|p| match &p.answer {
+                            Ok(ans) => ans.to_string(),
+                            Err(e) => format!("error: {}", e),
+                        })
+                        .collect();
+                    answers.join("  ")
+                }
+                Err(e) => format!("error: {}", e),
+            };
+
+            writeln!(
+                f,
+                "{:>3}  {:>9}  {:>9}  {:>9}  {:>9}  {}",
+                r.day,
+                DurationFmt(Some(r.parse_time)),
+                DurationFmt(r.part_time(0)),
+                DurationFmt(r.part_time(1)),
+                DurationFmt(Some(r.total_time())),
+                answers
+            )?;
+        }
+
+        let total = self.0.iter().map(Report::total_time).sum();
+        write!(
+            f,
+            "{:>3}  {:>9}  {:>9}  {:>9}  {:>9}",
+            "",
+            "",
+            "",
+            "",
+            DurationFmt(Some(total))
+        )
+    }
+}
+
+/// Human-readable durations, e.g. `12.3ms`. Shows `-` for None.
+pub struct DurationFmt(pub Option<Duration>);
+
+impl fmt::Display for DurationFmt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let d = match self.0 {
+            Some(d) => d,
+            None => return f.pad("-"),
+        };
+
+        let s = if d < Duration::from_millis(1) {
+            format!("{}µs", d.as_micros())
+        } else if d < Duration::from_secs(1) {
+            format!("{:.1}ms", d.as_secs_f64() * 1e3)
+        } else {
+            format!("{:.2}s", d.as_secs_f64())
+        };
+        f.pad(&s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_fmt() {
+        let fmt = |d| DurationFmt(Some(d)).to_string();
+        assert_eq!(fmt(Duration::from_micros(250)), "250µs");
+        assert_eq!(fmt(Duration::from_micros(12_345)), "12.3ms");
+        assert_eq!(fmt(Duration::from_millis(2_500)), "2.50s");
+        assert_eq!(format!("{:>3}", DurationFmt(None)), "  -");
+    }
+}
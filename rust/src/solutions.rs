@@ -1,13 +1,9 @@
+use crate::{Answer, Context, Error, Res};
+use std::io::BufRead;
+use std::marker::PhantomData;
+
 pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-//pub mod day7;
-//pub mod day8;
-//pub mod day9;
-//pub mod day10;
+pub mod day10;
 pub mod day11;
 pub mod day12;
 pub mod day13;
@@ -17,9 +13,199 @@ pub mod day16;
 pub mod day17;
 pub mod day18;
 pub mod day19;
+pub mod day2;
 pub mod day20;
 pub mod day21;
 pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// A single day's puzzle.
+///
+/// The input is parsed once, and then each part is solved from the parsed input.
+pub trait Solution {
+    /// Which day of the calendar this is, from 1 through 25.
+    const DAY: u32;
+
+    /// Day 25 only has one part.
+    const HAS_PART2: bool = true;
+
+    /// The parsed puzzle input.
+    type Input;
+
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Res<Self::Part1>;
+
+    /// Days without a part 2 can leave this out.
+    fn part2(_input: &Self::Input) -> Res<Self::Part2> {
+        Err(Error::invalid("There is no part 2"))
+    }
+}
+
+/// Every implemented day, in calendar order.
+pub static DAYS: [Day; 25] = [
+    Day::new::<day1::Day1>(),
+    Day::new::<day2::Day2>(),
+    Day::new::<day3::Day3>(),
+    Day::new::<day4::Day4>(),
+    Day::new::<day5::Day5>(),
+    Day::new::<day6::Day6>(),
+    Day::new::<day7::Day7>(),
+    Day::new::<day8::Day8>(),
+    Day::new::<day9::Day9>(),
+    Day::new::<day10::Day10>(),
+    Day::new::<day11::Day11>(),
+    Day::new::<day12::Day12>(),
+    Day::new::<day13::Day13>(),
+    Day::new::<day14::Day14>(),
+    Day::new::<day15::Day15>(),
+    Day::new::<day16::Day16>(),
+    Day::new::<day17::Day17>(),
+    Day::new::<day18::Day18>(),
+    Day::new::<day19::Day19>(),
+    Day::new::<day20::Day20>(),
+    Day::new::<day21::Day21>(),
+    Day::new::<day22::Day22>(),
+    Day::new::<day23::Day23>(),
+    Day::new::<day24::Day24>(),
+    Day::new::<day25::Day25>(),
+];
+
+/// Look up a day in the registry. Every day from 1 through 25 is there.
+pub fn get(day: u32) -> Res<&'static Day> {
+    DAYS.iter()
+        .find(|d| d.number == day)
+        .ok_or_else(|| Error::invalid(format!("No day {}, expected 1 through 25", day)))
+}
+
+/// An entry in the registry: a `Solution` with its types erased.
+pub struct Day {
+    pub number: u32,
+    parse: fn(&mut dyn BufRead) -> Res<Box<dyn Puzzle>>,
+}
+
+impl Day {
+    const fn new<S: Solution + 'static>() -> Day {
+        Day {
+            number: S::DAY,
+            parse: parse::<S>,
+        }
+    }
+
+    /// Parse the input, getting it ready to be solved.
+    pub fn parse(&self, input: &mut dyn BufRead) -> Res<Box<dyn Puzzle>> {
+        (self.parse)(input)
+    }
+}
+
+/// A parsed puzzle input.
+pub trait Puzzle {
+    fn part1(&self) -> Res<Answer>;
+
+    /// Whether there's a part 2, without solving it.
+    fn has_part2(&self) -> bool;
+
+    /// An error if there is no part 2.
+    fn part2(&self) -> Res<Answer>;
+}
+
+/// Helper for `Day::new`.
+fn parse<S: Solution + 'static>(input: &mut dyn BufRead) -> Res<Box<dyn Puzzle>> {
+    let input = S::parse(input).in_day(S::DAY)?;
+    Ok(Box::new(Parsed::<S>(input, PhantomData)))
+}
+
+/// The `Puzzle` behind each `Day`.
+struct Parsed<S: Solution>(S::Input, PhantomData<S>);
+
+impl<S: Solution> Puzzle for Parsed<S> {
+    fn part1(&self) -> Res<Answer> {
+        Ok(S::part1(&self.0).in_day(S::DAY)?.into())
+    }
+
+    fn has_part2(&self) -> bool {
+        S::HAS_PART2
+    }
+
+    fn part2(&self) -> Res<Answer> {
+        S::part2(&self.0).map(Into::into).in_day(S::DAY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_in_calendar_order() {
+        let numbers: Vec<_> = DAYS.iter().map(|d| d.number).collect();
+        let mut sorted = numbers.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(numbers, sorted);
+        assert!(numbers.iter().all(|n| (1..=25).contains(n)));
+    }
+
+    #[test]
+    fn missing_part2() -> Res<()> {
+        let puzzle = get(25)
+            .unwrap()
+            .parse(&mut "5764801\n17807724".as_bytes())?;
+        assert!(!puzzle.has_part2());
+        assert_eq!(
+            puzzle.part2().unwrap_err().to_string(),
+            "day 25: There is no part 2"
+        );
+        assert!(get(24).unwrap().parse(&mut "e".as_bytes())?.has_part2());
+        Ok(())
+    }
+
+    #[test]
+    fn no_such_day() {
+        for day in [0, 26] {
+            let e = crate::solution(day, &mut "".as_bytes()).unwrap_err();
+            assert_eq!(
+                e.to_string(),
+                format!("No day {}, expected 1 through 25", day)
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_inputs_are_errors() {
+        let cases = [
+            (18, "1 + (2 * 3"),
+            (18, "1 + + 2"),
+            (18, "1 + x"),
+            (19, "0: 1 2\n1: \"a\""),
+            (19, "0: 1\n1: \"a\"\n\nab"),
+            (19, "0: \"a\"\n0: \"b\"\n\nab"),
+            (22, "Player 1:\n1\n\nPlayer 3:\n2"),
+            (22, "Player 1:\n1\n\nPlayer 2:\nx"),
+            (23, "389125467\n1"),
+            (23, "38912546x"),
+            (23, "3891254"),
+            (24, "nwe\nesx"),
+            (24, "sen"),
+            (25, "5764801"),
+            (25, "5764801\n99999999"),
+        ];
+        for (day, input) in cases {
+            let res = crate::solution(day, &mut input.as_bytes());
+            assert!(res.is_err(), "day {}: {:?}", day, input);
+            assert_eq!(res.unwrap_err().location().day, Some(day));
+        }
+    }
+}
@@ -1,77 +1,300 @@
-use crate::Res;
-use std::collections::HashSet;
-use std::io::{self, prelude::*};
+use crate::{Context, Res, Solution};
+use num_traits::Zero;
+use std::io::prelude::*;
+use std::num::ParseIntError;
+use std::ops::Add;
+use std::str::FromStr;
 
-pub fn main() -> Res<()> {
-    let nums = read_nums(io::stdin().lock())?;
+pub struct Day1;
 
-    let (x, y) = two_sum(&nums, 2020).expect("No solution");
-    println!("{}", x * y);
+impl Solution for Day1 {
+    const DAY: u32 = 1;
 
-    let (x, y, z) = three_sum(&nums, 2020).expect("No solution");
-    println!("{}", x * y * z);
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
 
-    Ok(())
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<i32>> {
+        read_nums(input)
+    }
+
+    fn part1(nums: &Vec<i32>) -> Res<i32> {
+        product_of_entries(nums, 2, TARGET)
+    }
+
+    fn part2(nums: &Vec<i32>) -> Res<i32> {
+        product_of_entries(nums, 3, TARGET)
+    }
 }
 
-fn read_nums<R: BufRead>(input: R) -> Res<Vec<i32>> {
+const TARGET: i32 = 2020;
+
+/// Find `k` entries that add up to `target`, and multiply them together.
+fn product_of_entries(nums: &[i32], k: usize, target: i32) -> Res<i32> {
+    let combo = first_k_sum(nums, k, target).ok_or("No solution")?;
+    Ok(combo.iter().map(|&i| nums[i]).product())
+}
+
+/// Read one number per line. Days 9 and 10 use this too.
+pub fn read_nums<T, R>(input: R) -> Res<Vec<T>>
+where
+    T: FromStr<Err = ParseIntError>,
+    R: BufRead,
+{
     let mut nums = vec![];
-    for line in input.lines() {
-        let n: i32 = line?.parse()?;
+    for (i, line) in input.lines().enumerate() {
+        let n: T = line?.parse().at_line(i + 1)?;
         nums.push(n);
     }
     Ok(nums)
 }
 
-fn two_sum(nums: &[i32], target: i32) -> Option<(i32, i32)> {
-    let mut seen = HashSet::new();
-    for &x in nums {
-        let y = target - x;
-        if seen.contains(&y) {
-            return Some((y, x));
+/// Every combination of `k` distinct indices whose numbers add up to `target`.
+///
+/// Each combination is in increasing order, and so is the list of them. Equal numbers at
+/// different indices count as different combinations, e.g. `[1, 1, 1]` has three pairs that
+/// add up to 2.
+///
+/// The numbers are sorted first, so that the last two indices can be found with two pointers,
+/// and so that a partial combination can be dropped as soon as it's too big or too small.
+/// That's O(n^(k-1)) in the worst case, but much faster on typical inputs.
+pub fn k_sum<T: Num>(nums: &[T], k: usize, target: T) -> Vec<Vec<usize>> {
+    let mut search = KSum::new(nums, target, None);
+    search.run(k);
+    let mut found = search.found;
+    found.sort_unstable();
+    found
+}
+
+/// Like `k_sum`, but stop at the first combination found. Which one that is isn't specified.
+///
+/// Day 9 uses this too.
+pub fn first_k_sum<T: Num>(nums: &[T], k: usize, target: T) -> Option<Vec<usize>> {
+    let mut search = KSum::new(nums, target, Some(1));
+    search.run(k);
+    search.found.pop()
+}
+
+/// What `k_sum` needs from a number type. Only addition is used, so unsigned types are fine.
+pub trait Num: Copy + Ord + Add<Output = Self> + Zero {}
+
+impl<T: Copy + Ord + Add<Output = T> + Zero> Num for T {}
+
+/// State for `k_sum` and `first_k_sum`.
+struct KSum<T> {
+    /// (num, index) pairs, sorted by num.
+    sorted: Vec<(T, usize)>,
+    target: T,
+
+    /// Positions in `sorted` of the current partial combination.
+    chosen: Vec<usize>,
+
+    found: Vec<Vec<usize>>,
+
+    /// Stop after finding this many.
+    limit: Option<usize>,
+}
+
+impl<T: Num> KSum<T> {
+    fn new(nums: &[T], target: T, limit: Option<usize>) -> KSum<T> {
+        let mut sorted: Vec<_> = nums.iter().copied().zip(0..).collect();
+        sorted.sort_unstable();
+        KSum {
+            sorted,
+            target,
+            chosen: vec![],
+            found: vec![],
+            limit,
         }
-        seen.insert(x);
     }
-    None
-}
 
-fn three_sum(nums: &[i32], target: i32) -> Option<(i32, i32, i32)> {
-    let mut seen = HashSet::new();
-    for (i, &x) in nums.iter().enumerate() {
-        for &y in nums.iter().skip(i + 1) {
-            let z = target - x - y;
-            if seen.contains(&z) {
-                return Some((z, x, y));
+    fn run(&mut self, k: usize) {
+        if k <= self.sorted.len() {
+            self.search(0, k, T::zero());
+        }
+    }
+
+    fn done(&self) -> bool {
+        self.limit == Some(self.found.len())
+    }
+
+    fn val(&self, pos: usize) -> T {
+        self.sorted[pos].0
+    }
+
+    /// Record the chosen positions, plus `extra`, as a combination.
+    fn emit(&mut self, extra: &[usize]) {
+        let mut combo: Vec<_> = self
+            .chosen
+            .iter()
+            .chain(extra)
+            .map(|&pos| self.sorted[pos].1)
+            .collect();
+        combo.sort_unstable();
+        self.found.push(combo);
+    }
+
+    /// Choose `k` more positions from `start..`, given that the ones chosen so far add up to `sum`.
+    fn search(&mut self, start: usize, k: usize, sum: T) {
+        let n = self.sorted.len();
+        match k {
+            0 => {
+                if sum == self.target {
+                    self.emit(&[]);
+                }
+            }
+            1 => {
+                // All the positions with the right number.
+                let lo =
+                    start + self.sorted[start..].partition_point(|&(x, _)| sum + x < self.target);
+                let hi =
+                    start + self.sorted[start..].partition_point(|&(x, _)| sum + x <= self.target);
+                for pos in lo..hi {
+                    if self.done() {
+                        return;
+                    }
+                    self.emit(&[pos]);
+                }
+            }
+            2 => self.two_pointers(start, sum),
+            _ => {
+                for pos in start..=n - k {
+                    // Too big, even with the smallest numbers left? Then so is everything after.
+                    let smallest = (pos..pos + k).fold(sum, |acc, p| acc + self.val(p));
+                    if smallest > self.target || self.done() {
+                        return;
+                    }
+                    // Too small, even with the biggest numbers left?
+                    let biggest =
+                        (n - k + 1..n).fold(sum + self.val(pos), |acc, p| acc + self.val(p));
+                    if biggest < self.target {
+                        continue;
+                    }
+
+                    self.chosen.push(pos);
+                    self.search(pos + 1, k - 1, sum + self.val(pos));
+                    self.chosen.pop();
+                }
+            }
+        }
+    }
+
+    /// `search` for the last two positions.
+    fn two_pointers(&mut self, start: usize, sum: T) {
+        let mut lo = start;
+        let mut hi = self.sorted.len().saturating_sub(1);
+        while lo < hi && !self.done() {
+            let (x, y) = (self.val(lo), self.val(hi));
+            let pair_sum = sum + x + y;
+            if pair_sum < self.target {
+                lo += 1;
+            } else if pair_sum > self.target {
+                hi -= 1;
+            } else if x == y {
+                // Everything in lo..=hi is equal, so any two of them work.
+                for i in lo..hi {
+                    for j in i + 1..=hi {
+                        if self.done() {
+                            return;
+                        }
+                        self.emit(&[i, j]);
+                    }
+                }
+                return;
+            } else {
+                // Pair up each copy of x with each copy of y.
+                let lo_end = (lo..=hi).find(|&p| self.val(p) != x).unwrap();
+                let hi_start = (lo..=hi).rev().find(|&p| self.val(p) != y).unwrap() + 1;
+                for i in lo..lo_end {
+                    for j in hi_start..=hi {
+                        if self.done() {
+                            return;
+                        }
+                        self.emit(&[i, j]);
+                    }
+                }
+                lo = lo_end;
+                hi = hi_start - 1;
             }
         }
-        seen.insert(x);
     }
-    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
-    use std::io::BufReader;
+    use crate::generators::Rng;
+    use crate::open_input;
 
     #[test]
     fn part1() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/1")?);
-        let nums = read_nums(input)?;
-
-        let (x, y) = two_sum(&nums, 2020).unwrap();
-        assert_eq!(x * y, 731731);
+        let nums = read_nums(open_input(1)?)?;
+        assert_eq!(product_of_entries(&nums, 2, TARGET)?, 731731);
         Ok(())
     }
 
     #[test]
     fn part2() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/1")?);
-        let nums = read_nums(input)?;
-
-        let (x, y, z) = three_sum(&nums, 2020).unwrap();
-        assert_eq!(x * y * z, 116115990);
+        let nums = read_nums(open_input(1)?)?;
+        assert_eq!(product_of_entries(&nums, 3, TARGET)?, 116115990);
         Ok(())
     }
+
+    #[test]
+    fn duplicates() {
+        let nums = [1_u32, 1, 2, 1];
+        assert_eq!(k_sum(&nums, 2, 2), vec![vec![0, 1], vec![0, 3], vec![1, 3]]);
+        assert_eq!(k_sum(&nums, 2, 3).len(), 3);
+        assert_eq!(
+            k_sum(&nums, 3, 4),
+            vec![vec![0, 1, 2], vec![0, 2, 3], vec![1, 2, 3]]
+        );
+        assert_eq!(k_sum(&nums, 0, 0), vec![vec![]]);
+        assert!(k_sum(&nums, 5, 5).is_empty());
+        assert!(first_k_sum(&nums, 4, 6).is_none());
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        // Every k-subset of 0..n, in increasing order.
+        fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+            if k == 0 {
+                return vec![vec![]];
+            }
+            let mut ret = vec![];
+            for last in k - 1..n {
+                for mut s in subsets(last, k - 1) {
+                    s.push(last);
+                    ret.push(s);
+                }
+            }
+            ret.sort_unstable();
+            ret
+        }
+
+        let mut rng = Rng::new(1);
+        for _ in 0..200 {
+            let n = rng.between(0, 9) as usize;
+            let k = rng.between(0, 5) as usize;
+            let nums: Vec<_> = (0..n).map(|_| rng.between(0, 6) as i64 - 3).collect();
+            let target = rng.between(0, 8) as i64 - 4;
+
+            let expected: Vec<_> = subsets(n, k)
+                .into_iter()
+                .filter(|s| s.iter().map(|&i| nums[i]).sum::<i64>() == target)
+                .collect();
+            assert_eq!(
+                k_sum(&nums, k, target),
+                expected,
+                "{:?} {} {}",
+                nums,
+                k,
+                target
+            );
+            assert_eq!(
+                first_k_sum(&nums, k, target).is_some(),
+                !expected.is_empty()
+            );
+        }
+    }
 }
@@ -0,0 +1,104 @@
+use super::day1::read_nums;
+use crate::{Res, Solution};
+use std::io::prelude::*;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = Vec<u64>;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    /// The joltages of the whole chain, in order: the outlet, every adapter, and the device.
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<u64>> {
+        let adapters = read_nums(input)?;
+        chain(adapters)
+    }
+
+    fn part1(chain: &Vec<u64>) -> Res<usize> {
+        let counts = diff_counts(chain);
+        Ok(counts[1] * counts[3])
+    }
+
+    fn part2(chain: &Vec<u64>) -> Res<u64> {
+        Ok(num_arrangements(chain))
+    }
+}
+
+/// Sort the adapters, and add the outlet (0 jolts) and the device (3 more than the last adapter).
+///
+/// Returns an error if the adapters can't all be used, or if two have the same joltage.
+fn chain(mut adapters: Vec<u64>) -> Res<Vec<u64>> {
+    adapters.push(0);
+    adapters.sort_unstable();
+    let device = adapters.last().unwrap() + 3;
+    adapters.push(device);
+
+    for pair in adapters.windows(2) {
+        match pair[1] - pair[0] {
+            0 => return Err(format!("Two adapters are rated {} jolts", pair[0]).into()),
+            1..=3 => (),
+            _ => {
+                return Err(
+                    format!("Gap in the chain from {} to {} jolts", pair[0], pair[1]).into(),
+                )
+            }
+        }
+    }
+    Ok(adapters)
+}
+
+/// How many times each difference (from 0 to 3 jolts) occurs between consecutive links.
+fn diff_counts(chain: &[u64]) -> [usize; 4] {
+    let mut counts = [0; 4];
+    for pair in chain.windows(2) {
+        counts[(pair[1] - pair[0]) as usize] += 1;
+    }
+    counts
+}
+
+/// How many ways are there to get from the outlet to the device, skipping some adapters?
+fn num_arrangements(chain: &[u64]) -> u64 {
+    // `num_paths[i]` is the number of ways to get from chain[0] to chain[i].
+    let mut num_paths = vec![0; chain.len()];
+    num_paths[0] = 1;
+
+    for i in 1..chain.len() {
+        num_paths[i] = (i.saturating_sub(3)..i)
+            .filter(|&j| chain[i] - chain[j] <= 3)
+            .map(|j| num_paths[j])
+            .sum();
+    }
+
+    num_paths[chain.len() - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_input;
+
+    #[test]
+    fn example() -> Res<()> {
+        let chain = chain(vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4])?;
+        assert_eq!(diff_counts(&chain), [0, 7, 0, 5]);
+        assert_eq!(num_arrangements(&chain), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn part1() -> Res<()> {
+        let chain = chain(read_nums(open_input(10)?)?)?;
+        assert_eq!(Day10::part1(&chain)?, 2400);
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> Res<()> {
+        let chain = chain(read_nums(open_input(10)?)?)?;
+        assert_eq!(Day10::part2(&chain)?, 338510590509056);
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//! Write each generation of a simulation to a file, to watch how the seating evolves.
+//!
+//! Frames are named `frame-0000.txt` and so on, numbered by generation, so that e.g.
+//! `ffmpeg -i frame-%04d.ppm` can turn the images into an animation.
+
+use super::rules::Rules;
+use super::sim::{self, Outcome};
+use super::{State, Tile};
+use crate::{Error, Res};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A sensible limit on the number of generations to export, since each one is a file.
+pub const MAX_FRAMES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The puzzle's notation: `#`, `L`, and `.`.
+    Text,
+
+    /// Greyscale: white for people, grey for empty seats, and black for the floor.
+    Pgm,
+
+    /// Colour: red for people, green for empty seats, and dark grey for the floor.
+    Ppm,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Text => "txt",
+            Format::Pgm => "pgm",
+            Format::Ppm => "ppm",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Res<Format> {
+        match s {
+            "text" | "txt" => Ok(Format::Text),
+            "pgm" => Ok(Format::Pgm),
+            "ppm" => Ok(Format::Ppm),
+            _ => Err(Error::invalid(format!(
+                "unknown frame format `{}`, expected `text`, `pgm`, or `ppm`",
+                s
+            ))),
+        }
+    }
+}
+
+/// Write one generation. Images are binary (P5 and P6), with one pixel per tile.
+pub fn write_frame(state: &State, format: Format, out: &mut impl Write) -> io::Result<()> {
+    let (n, m) = state.dimensions();
+    match format {
+        Format::Text => write!(out, "{}", state),
+        Format::Pgm => {
+            write!(out, "P5\n{} {}\n255\n", m, n)?;
+            let pixels: Vec<_> = state.tiles.iter().map(|&t| grey(t)).collect();
+            out.write_all(&pixels)
+        }
+        Format::Ppm => {
+            write!(out, "P6\n{} {}\n255\n", m, n)?;
+            let pixels: Vec<_> = state.tiles.iter().flat_map(|&t| colour(t)).collect();
+            out.write_all(&pixels)
+        }
+    }
+}
+
+fn grey(tile: Tile) -> u8 {
+    match tile {
+        Tile::Person => 255,
+        Tile::Chair => 128,
+        Tile::Floor => 0,
+    }
+}
+
+fn colour(tile: Tile) -> [u8; 3] {
+    match tile {
+        Tile::Person => [220, 50, 47],
+        Tile::Chair => [133, 153, 0],
+        Tile::Floor => [40, 40, 40],
+    }
+}
+
+/// Simulate, as in `sim::watch`, writing each generation to a file in `dir`, which is created
+/// if need be. It has to be empty, so that frames from an earlier run don't get mixed in.
+///
+/// Returns how the simulation ended, and how many frames were written.
+pub fn export(
+    initial: &State,
+    rules: &Rules,
+    max_steps: usize,
+    format: Format,
+    dir: &Path,
+) -> Res<(Outcome, usize)> {
+    fs::create_dir_all(dir)?;
+    if fs::read_dir(dir)?.next().is_some() {
+        let msg = format!(
+            "{} isn't empty; frames need a directory of their own",
+            dir.display()
+        );
+        return Err(Error::invalid(msg));
+    }
+
+    let mut num_frames = 0;
+    let outcome = sim::watch(initial, rules, max_steps, |step, state| -> Res<()> {
+        let path = dir.join(format!("frame-{:04}.{}", step, format.extension()));
+        let mut out = BufWriter::new(File::create(path)?);
+        write_frame(state, format, &mut out)?;
+        out.flush()?;
+        num_frames += 1;
+        Ok(())
+    })?;
+
+    Ok((outcome, num_frames))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames() -> Res<()> {
+        let state = State::from_input("L.L\n#.L".as_bytes())?;
+
+        let mut text = vec![];
+        write_frame(&state, Format::Text, &mut text)?;
+        assert_eq!(text, b"L.L\n#.L\n");
+
+        let mut pgm = vec![];
+        write_frame(&state, Format::Pgm, &mut pgm)?;
+        assert_eq!(pgm, b"P5\n3 2\n255\n\x80\x00\x80\xff\x00\x80");
+
+        let mut ppm = vec![];
+        write_frame(&state, Format::Ppm, &mut ppm)?;
+        assert_eq!(ppm.len(), "P6\n3 2\n255\n".len() + 3 * 6);
+
+        assert_eq!("txt".parse::<Format>()?, Format::Text);
+        assert!("gif".parse::<Format>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn export_frames() -> Res<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-2020-day11-{}", std::process::id()));
+        let state = State::from_input("LL\nLL".as_bytes())?;
+
+        // Everyone sits down, and then nothing changes: two distinct frames.
+        let result = export(&state, &Rules::PART1, 100, Format::Text, &dir);
+        // Exporting again would mix in the old frames.
+        let second = export(&state, &Rules::PART2, 100, Format::Text, &dir);
+        let frames: Vec<_> = (0..3)
+            .map(|i| fs::read_to_string(dir.join(format!("frame-{:04}.txt", i))).ok())
+            .collect();
+        let _ = fs::remove_dir_all(&dir);
+
+        let (outcome, num_frames) = result?;
+        assert!(matches!(outcome, Outcome::Stable { steps: 1, .. }));
+        assert!(second.is_err());
+        assert_eq!(num_frames, 2);
+        assert_eq!(
+            frames,
+            [
+                Some(String::from("LL\nLL\n")),
+                Some(String::from("##\n##\n")),
+                None
+            ]
+        );
+        Ok(())
+    }
+}
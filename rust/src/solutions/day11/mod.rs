@@ -0,0 +1,245 @@
+use crate::{Context, Error, Res, Solution};
+use neighbours::NeighbourTable;
+use rules::Rules;
+use sim::Outcome;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::prelude::*;
+
+pub mod frames;
+pub mod neighbours;
+pub mod rules;
+pub mod sim;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input = State;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &mut dyn BufRead) -> Res<State> {
+        State::from_input(input)
+    }
+
+    fn part1(initial_state: &State) -> Res<usize> {
+        final_num_ppl(initial_state, &Rules::PART1)
+    }
+
+    fn part2(initial_state: &State) -> Res<usize> {
+        final_num_ppl(initial_state, &Rules::PART2)
+    }
+}
+
+/// Give up on the official rules after this many generations. The real input settles in
+/// about 100.
+const MAX_STEPS: usize = 10_000;
+
+/// How many seats are occupied once the seating stops changing?
+fn final_num_ppl(initial_state: &State, rules: &Rules) -> Res<usize> {
+    match sim::simulate(initial_state, rules, MAX_STEPS) {
+        Outcome::Stable { state, .. } => Ok(state.total_num_ppl()),
+        Outcome::Cycle { start, period, .. } => {
+            let msg = format!(
+                "Never settles: repeats every {} generations from {}",
+                period, start
+            );
+            Err(msg.into())
+        }
+        Outcome::GaveUp { steps, .. } => {
+            Err(format!("Didn't settle in {} generations", steps).into())
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Chair,
+    Person,
+    Floor,
+}
+
+impl Tile {
+    fn new(c: char) -> Res<Tile> {
+        match c {
+            '#' => Ok(Tile::Person),
+            'L' => Ok(Tile::Chair),
+            '.' => Ok(Tile::Floor),
+            _ => Err(Error::invalid_char(c)),
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Tile::Person => '#',
+            Tile::Chair => 'L',
+            Tile::Floor => '.',
+        }
+    }
+}
+
+/// The seating, stored row by row in one flat vector.
+#[derive(Clone, PartialEq, Eq)]
+pub struct State {
+    tiles: Vec<Tile>,
+    width: usize,
+}
+
+impl State {
+    fn from_input(input: impl BufRead) -> Res<State> {
+        let mut tiles = vec![];
+        let mut width = 0;
+        for (i, line) in input.lines().enumerate() {
+            let row: Res<Vec<_>> = line?
+                .chars()
+                .enumerate()
+                .map(|(j, c)| Tile::new(c).at(i + 1, j + 1))
+                .collect();
+            let row = row?;
+            if i == 0 {
+                width = row.len();
+            } else if row.len() != width {
+                let msg = format!("Jagged grid: row lengths {} and {}", width, row.len());
+                return Err(Error::malformed(msg).at_line(i + 1));
+            }
+            tiles.extend(row);
+        }
+        Ok(State { tiles, width })
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        let n = self.tiles.len().checked_div(self.width).unwrap_or(0);
+        (n, self.width)
+    }
+
+    /// Write the next generation into `output_buf`, which must have the same floor plan that
+    /// `neighbours` was built from. Returns whether any seat changed.
+    ///
+    /// Floor tiles in `output_buf` are left alone.
+    fn evolve(&self, output_buf: &mut State, neighbours: &NeighbourTable, rules: &Rules) -> bool {
+        assert_eq!(output_buf.tiles.len(), self.tiles.len());
+
+        let mut changed = false;
+        for (idx, visible) in neighbours.iter() {
+            let tile = self.tiles[idx];
+            let nearby_ppl = visible
+                .iter()
+                .filter(|&&seat| self.tiles[seat] == Tile::Person)
+                .count();
+            let new_tile = match tile {
+                Tile::Chair if nearby_ppl <= rules.birth as usize => Tile::Person,
+                Tile::Person if nearby_ppl >= rules.death as usize => Tile::Chair,
+                _ => tile,
+            };
+            changed |= new_tile != tile;
+            output_buf.tiles[idx] = new_tile;
+        }
+        changed
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Tile]> {
+        // `chunks` panics on 0.
+        self.tiles.chunks(self.width.max(1))
+    }
+
+    fn total_num_ppl(&self) -> usize {
+        self.tiles.iter().filter(|&&t| t == Tile::Person).count()
+    }
+}
+
+/// The same notation as the input, one row per line.
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            let row: String = row.iter().map(|&t| t.to_char()).collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Hashes a byte per tile in one go, which is much faster than hashing each tile separately.
+impl Hash for State {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let bytes: Vec<u8> = self.tiles.iter().map(|&t| t as u8).collect();
+        state.write(&bytes);
+        self.width.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_input;
+
+    #[test]
+    fn part1() -> Res<()> {
+        let input = open_input(11)?;
+        let initial_state = State::from_input(input)?;
+        assert_eq!(final_num_ppl(&initial_state, &Rules::PART1)?, 2319);
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> Res<()> {
+        let input = open_input(11)?;
+        let initial_state = State::from_input(input)?;
+        assert_eq!(final_num_ppl(&initial_state, &Rules::PART2)?, 2117);
+        Ok(())
+    }
+
+    #[test]
+    fn cycles() -> Res<()> {
+        use rules::Neighbourhood;
+
+        // Two seats next to each other fill up together, then crowd each other out.
+        let state = State::from_input("LL".as_bytes())?;
+        let rules = Rules {
+            neighbourhood: Neighbourhood::VonNeumann,
+            sight: Some(1),
+            birth: 0,
+            death: 1,
+        };
+        match sim::simulate(&state, &rules, 100) {
+            Outcome::Cycle { start, period, .. } => assert_eq!((start, period), (0, 2)),
+            _ => panic!("Expected a cycle"),
+        }
+        assert!(final_num_ppl(&state, &rules).is_err());
+
+        // With the floor in between, the seats can't see each other unless the sight is long.
+        let state = State::from_input("L..L".as_bytes())?;
+        let near = Rules {
+            sight: Some(2),
+            ..rules
+        };
+        assert!(matches!(
+            sim::simulate(&state, &near, 100),
+            Outcome::Stable { steps: 1, .. }
+        ));
+        let far = Rules {
+            sight: None,
+            ..rules
+        };
+        assert!(matches!(
+            sim::simulate(&state, &far, 1),
+            Outcome::GaveUp { steps: 1, .. }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() {
+        let e = crate::solution(11, &mut "L.L\nL#x".as_bytes()).unwrap_err();
+        assert!(matches!(e, Error::InvalidChar { c: 'x', .. }));
+        assert_eq!(
+            e.to_string(),
+            "day 11, line 2, column 3: invalid character 'x'"
+        );
+
+        let e = State::from_input("L.L\nL#".as_bytes()).err().unwrap();
+        assert!(matches!(e, Error::MalformedLine { .. }));
+        assert_eq!(e.location().line, Some(2));
+    }
+}
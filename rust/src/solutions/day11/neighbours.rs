@@ -0,0 +1,129 @@
+use super::rules::Rules;
+use super::{State, Tile};
+
+/// For each seat, the seats it can see, under some rules.
+///
+/// Seats never turn into floor or vice versa, so this only depends on the floor plan, and can
+/// be reused for every generation.
+#[derive(Clone)]
+pub struct NeighbourTable {
+    /// Index of each seat in the grid.
+    seats: Vec<usize>,
+
+    /// The grid indices of the seats that seat `k` can see are
+    /// `visible[starts[k]..starts[k + 1]]`.
+    starts: Vec<usize>,
+    visible: Vec<usize>,
+}
+
+/// No seat in this direction, during construction.
+const NONE: usize = usize::MAX;
+
+impl NeighbourTable {
+    /// Takes O(tiles * directions) time, however far the rules let seats see.
+    pub fn new(state: &State, rules: &Rules) -> NeighbourTable {
+        let (n, m) = state.dimensions();
+        let directions = rules.neighbourhood.directions();
+        let sight = rules.sight.unwrap_or(usize::MAX);
+
+        let seats: Vec<_> = (0..n * m)
+            .filter(|&idx| state.tiles[idx] != Tile::Floor)
+            .collect();
+        let stride = directions.len();
+        let mut table = vec![NONE; seats.len() * stride];
+
+        // (first seat, distance to it) in the current direction, for each tile.
+        let mut nearest: Vec<Option<(usize, usize)>> = vec![None; n * m];
+
+        for (d, &(di, dj)) in directions.iter().enumerate() {
+            // A tile sees either its neighbour, or whatever its neighbour sees. So visit the
+            // neighbours first.
+            let rows: Vec<_> = if di > 0 {
+                (0..n).rev().collect()
+            } else {
+                (0..n).collect()
+            };
+            let cols: Vec<_> = if dj > 0 {
+                (0..m).rev().collect()
+            } else {
+                (0..m).collect()
+            };
+
+            for &i in &rows {
+                for &j in &cols {
+                    let r = i as isize + di;
+                    let c = j as isize + dj;
+                    nearest[i * m + j] = if 0 <= r && r < n as isize && 0 <= c && c < m as isize {
+                        let nbr = r as usize * m + c as usize;
+                        if state.tiles[nbr] == Tile::Floor {
+                            nearest[nbr].map(|(seat, dist)| (seat, dist.saturating_add(1)))
+                        } else {
+                            Some((nbr, 1))
+                        }
+                    } else {
+                        None
+                    };
+                }
+            }
+
+            for (k, &idx) in seats.iter().enumerate() {
+                if let Some((seat, dist)) = nearest[idx] {
+                    if dist <= sight {
+                        table[k * stride + d] = seat;
+                    }
+                }
+            }
+        }
+
+        // Squeeze out the gaps.
+        let mut starts = Vec::with_capacity(seats.len() + 1);
+        let mut visible = vec![];
+        starts.push(0);
+        for row in table.chunks_exact(stride) {
+            visible.extend(row.iter().filter(|&&seat| seat != NONE));
+            starts.push(visible.len());
+        }
+
+        NeighbourTable {
+            seats,
+            starts,
+            visible,
+        }
+    }
+
+    /// Each seat's grid index, with the grid indices of the seats it can see.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &[usize])> + '_ {
+        self.seats
+            .iter()
+            .zip(self.starts.windows(2))
+            .map(move |(&idx, w)| (idx, &self.visible[w[0]..w[1]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Res;
+
+    #[test]
+    fn sight() -> Res<()> {
+        let state = State::from_input(".L.L\nL...\n..L.".as_bytes())?;
+        let visible = |rules: &Rules| -> Vec<(usize, Vec<usize>)> {
+            let table = NeighbourTable::new(&state, rules);
+            table
+                .iter()
+                .map(|(idx, seats)| (idx, seats.to_vec()))
+                .collect()
+        };
+
+        assert_eq!(
+            visible(&Rules::PART1),
+            [(1, vec![4]), (3, vec![]), (4, vec![1]), (10, vec![])]
+        );
+        assert_eq!(
+            visible(&Rules::PART2),
+            [(1, vec![3, 4]), (3, vec![1]), (4, vec![1]), (10, vec![])]
+        );
+        Ok(())
+    }
+}
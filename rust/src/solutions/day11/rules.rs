@@ -0,0 +1,60 @@
+/// How seats decide whether to fill up or empty out, in each generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub neighbourhood: Neighbourhood,
+
+    /// How far to look in each direction for the first seat, past any floor. `Some(1)` only
+    /// looks at adjacent tiles, and `None` looks all the way to the edge.
+    pub sight: Option<usize>,
+
+    /// An empty seat fills up if at most this many of its neighbours are occupied.
+    pub birth: u8,
+
+    /// An occupied seat empties if at least this many of its neighbours are occupied.
+    pub death: u8,
+}
+
+/// Which directions count as neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// All 8 directions, including diagonals.
+    Moore,
+
+    /// Up, down, left, and right.
+    VonNeumann,
+}
+
+impl Rules {
+    pub const PART1: Rules = Rules {
+        neighbourhood: Neighbourhood::Moore,
+        sight: Some(1),
+        birth: 0,
+        death: 4,
+    };
+
+    pub const PART2: Rules = Rules {
+        neighbourhood: Neighbourhood::Moore,
+        sight: None,
+        birth: 0,
+        death: 5,
+    };
+}
+
+impl Neighbourhood {
+    /// (row, column) steps.
+    pub fn directions(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Moore => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+            Neighbourhood::VonNeumann => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use super::neighbours::NeighbourTable;
+use super::rules::Rules;
+use super::State;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::hash::{Hash, Hasher};
+use std::mem;
+
+/// How a simulation ended.
+#[derive(Clone)]
+pub enum Outcome {
+    /// Nothing changes after `steps` generations.
+    Stable { steps: usize, state: State },
+
+    /// The state after `start` generations comes back every `period` generations, where
+    /// `period` is at least 2. `state` is the one after `start` generations.
+    Cycle {
+        start: usize,
+        period: usize,
+        state: State,
+    },
+
+    /// Neither happened within the limit. `state` is the last one.
+    GaveUp { steps: usize, state: State },
+}
+
+/// A simulation in progress. Each generation is written into a second buffer, which is then
+/// swapped in, so nothing is allocated after the start.
+#[derive(Clone)]
+pub struct Simulation {
+    rules: Rules,
+    neighbours: NeighbourTable,
+    curr: State,
+    next: State,
+    steps: usize,
+}
+
+impl Simulation {
+    pub fn new(initial: &State, rules: &Rules) -> Simulation {
+        Simulation {
+            rules: *rules,
+            neighbours: NeighbourTable::new(initial, rules),
+            curr: initial.clone(),
+            next: initial.clone(),
+            steps: 0,
+        }
+    }
+
+    /// Advance one generation. Returns whether anything changed.
+    pub fn step(&mut self) -> bool {
+        let changed = self
+            .curr
+            .evolve(&mut self.next, &self.neighbours, &self.rules);
+        mem::swap(&mut self.curr, &mut self.next);
+        self.steps += 1;
+        changed
+    }
+
+    pub fn state(&self) -> &State {
+        &self.curr
+    }
+
+    /// How many generations have passed.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+}
+
+/// Evolve `initial` until it stops changing or starts repeating, for at most `max_steps`
+/// generations.
+///
+/// Repeats are spotted by hash, so only the hashes of earlier states are kept. A repeated hash
+/// is confirmed by simulating one more period, in case two states happen to share a hash.
+pub fn simulate(initial: &State, rules: &Rules, max_steps: usize) -> Outcome {
+    watch(initial, rules, max_steps, |_, _| Ok::<_, Infallible>(()))
+        .unwrap_or_else(|never| match never {})
+}
+
+/// Like `simulate`, but calls `observe` with each distinct generation, and its number, starting
+/// with `initial` as generation 0. Stops early if `observe` fails.
+///
+/// A stable state is only observed once. A cycle is observed until it gets back to its start.
+pub fn watch<E>(
+    initial: &State,
+    rules: &Rules,
+    max_steps: usize,
+    mut observe: impl FnMut(usize, &State) -> Result<(), E>,
+) -> Result<Outcome, E> {
+    let mut sim = Simulation::new(initial, rules);
+    observe(0, sim.state())?;
+
+    // {hash -> generation}
+    let mut seen = HashMap::new();
+    seen.insert(hash(sim.state()), 0);
+
+    while sim.steps() < max_steps {
+        if !sim.step() {
+            return Ok(Outcome::Stable {
+                steps: sim.steps() - 1,
+                state: sim.curr,
+            });
+        }
+
+        let step = sim.steps();
+        observe(step, sim.state())?;
+
+        let h = hash(sim.state());
+        if let Some(&start) = seen.get(&h) {
+            let period = step - start;
+            if repeats_after(&sim, period) {
+                return Ok(Outcome::Cycle {
+                    start,
+                    period,
+                    state: sim.curr,
+                });
+            }
+        }
+        seen.insert(h, step);
+    }
+
+    Ok(Outcome::GaveUp {
+        steps: max_steps,
+        state: sim.curr,
+    })
+}
+
+fn hash(state: &State) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Helper for `simulate`.
+fn repeats_after(sim: &Simulation, period: usize) -> bool {
+    let mut ahead = sim.clone();
+    for _ in 0..period {
+        ahead.step();
+    }
+    ahead.state() == sim.state()
+}
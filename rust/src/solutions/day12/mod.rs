@@ -1,41 +1,61 @@
-use crate::Res;
-use std::io;
+use crate::{Res, Solution};
+use std::io::BufRead;
 use types::Action::{self, Forward, Rotate, Translate};
 use types::{Direction, Point};
 
-mod types;
+pub mod trajectory;
+pub mod types;
 
-pub fn main() -> Res<()> {
-    let actions = Action::read_actions(io::stdin().lock())?;
-    println!("{}", part1(&actions));
-    println!("{}", part2(&actions));
-    Ok(())
-}
+pub struct Day12;
 
-fn part1(actions: &[Action]) -> u32 {
-    let mut ship = Ship::new();
-    for &a in actions {
-        ship.take_action_1(a);
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = Vec<Action>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<Action>> {
+        Action::read_actions(input)
+    }
+
+    fn part1(actions: &Vec<Action>) -> Res<u32> {
+        Ok(distance_travelled(actions, Mode::Heading))
+    }
+
+    fn part2(actions: &Vec<Action>) -> Res<u32> {
+        Ok(distance_travelled(actions, Mode::Waypoint))
     }
-    ship.pos.manhattan_norm()
 }
 
-fn part2(actions: &[Action]) -> u32 {
+/// How far from the start the ship ends up.
+fn distance_travelled(actions: &[Action], mode: Mode) -> u32 {
     let mut ship = Ship::new();
     for &a in actions {
-        ship.take_action_2(a);
+        ship.take_action(a, mode);
     }
     ship.pos.manhattan_norm()
 }
 
-struct Ship {
-    pos: Point,
-    dir: Direction,
-    waypoint: Point,
+/// How to interpret the actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Part 1: move the ship, and turn its heading. The waypoint is ignored.
+    Heading,
+
+    /// Part 2: mostly move the waypoint, which is relative to the ship. The heading is ignored.
+    Waypoint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ship {
+    pub pos: Point,
+    pub dir: Direction,
+    pub waypoint: Point,
 }
 
 impl Ship {
-    fn new() -> Ship {
+    pub fn new() -> Ship {
         Ship {
             pos: Point::ORIGIN,
             dir: Direction::E,
@@ -43,44 +63,55 @@ impl Ship {
         }
     }
 
-    /// Part 1 "actions"; ignores waypoint.
-    fn take_action_1(&mut self, a: Action) {
-        match a {
-            Forward(n) => self.pos += Point::cardinal(self.dir) * n,
-            Translate(d, n) => self.pos += Point::cardinal(d) * n,
-            Rotate(r) => self.dir = self.dir.rotate(r),
+    pub fn take_action(&mut self, a: Action, mode: Mode) {
+        match (mode, a) {
+            (Mode::Heading, Forward(n)) => self.pos += Point::cardinal(self.dir) * n,
+            (Mode::Heading, Translate(d, n)) => self.pos += Point::cardinal(d) * n,
+            (Mode::Heading, Rotate(r)) => self.dir = self.dir.rotate(r),
+
+            (Mode::Waypoint, Forward(n)) => self.pos += self.waypoint * n,
+            (Mode::Waypoint, Translate(d, n)) => self.waypoint += Point::cardinal(d) * n,
+            (Mode::Waypoint, Rotate(r)) => self.waypoint = self.waypoint.rotate_about_origin(r),
         }
     }
+}
 
-    /// Part 2 "actions"; mostly moves the waypoint.
-    fn take_action_2(&mut self, a: Action) {
-        match a {
-            Forward(n) => self.pos += self.waypoint * n,
-            Translate(d, n) => self.waypoint += Point::cardinal(d) * n,
-            Rotate(r) => self.waypoint = self.waypoint.rotate_about_origin(r),
-        }
+impl Default for Ship {
+    fn default() -> Ship {
+        Ship::new()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
-    use std::io::BufReader;
+    use crate::open_input;
 
     #[test]
     fn part1() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/12")?);
+        let input = open_input(12)?;
         let actions = Action::read_actions(input)?;
-        assert_eq!(super::part1(&actions), 415);
+        assert_eq!(distance_travelled(&actions, Mode::Heading), 415);
         Ok(())
     }
 
     #[test]
     fn part2() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/12")?);
+        let input = open_input(12)?;
         let actions = Action::read_actions(input)?;
-        assert_eq!(super::part2(&actions), 29401);
+        assert_eq!(distance_travelled(&actions, Mode::Waypoint), 29401);
+        Ok(())
+    }
+
+    #[test]
+    fn full_turns() -> Res<()> {
+        let actions = Action::read_actions("R360\nL720\nF3".as_bytes())?;
+        let mut ship = Ship::new();
+        for &a in &actions {
+            ship.take_action(a, Mode::Heading);
+        }
+        assert_eq!(ship.dir, Direction::E);
+        assert_eq!(ship.pos, Point { x: 3, y: 0 });
         Ok(())
     }
 }
@@ -0,0 +1,190 @@
+//! Record every step of a voyage, rather than just where it ends up, so that it can be
+//! inspected, or drawn.
+
+use super::types::{Action, Point};
+use super::{Mode, Ship};
+use std::io::{self, Write};
+
+/// The ship after each action, starting with the ship before any of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    pub ships: Vec<Ship>,
+}
+
+/// The smallest rectangle containing every position, inclusive on all sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Trajectory {
+    pub fn record(actions: &[Action], mode: Mode) -> Trajectory {
+        let mut ship = Ship::new();
+        let mut ships = Vec::with_capacity(actions.len() + 1);
+        ships.push(ship);
+        for &a in actions {
+            ship.take_action(a, mode);
+            ships.push(ship);
+        }
+        Trajectory { ships }
+    }
+
+    pub fn end(&self) -> &Ship {
+        self.ships.last().unwrap()
+    }
+
+    /// Of the ship's positions; waypoints aren't included.
+    pub fn bounds(&self) -> Bounds {
+        let mut min = self.ships[0].pos;
+        let mut max = min;
+        for ship in &self.ships {
+            min.x = min.x.min(ship.pos.x);
+            min.y = min.y.min(ship.pos.y);
+            max.x = max.x.max(ship.pos.x);
+            max.y = max.y.max(ship.pos.y);
+        }
+        Bounds { min, max }
+    }
+
+    /// The step at which the ship is farthest from the start, by Manhattan distance, and that
+    /// distance. The first step wins ties.
+    pub fn max_distance(&self) -> (usize, u32) {
+        let mut best = (0, 0);
+        for (i, ship) in self.ships.iter().enumerate() {
+            let dist = ship.pos.manhattan_norm();
+            if dist > best.1 {
+                best = (i, dist);
+            }
+        }
+        best
+    }
+
+    /// Draw the ship's path, with north up. The start is green, the end is red, and the point
+    /// farthest from the start is blue.
+    pub fn write_svg(&self, out: &mut impl Write) -> io::Result<()> {
+        let Bounds { min, max } = self.bounds();
+        let (w, h) = (max.x - min.x, max.y - min.y);
+        let margin = (w.max(h) / 20).max(1);
+
+        // SVG's y axis points down, so negate every y.
+        let view = (
+            min.x - margin,
+            -max.y - margin,
+            w + 2 * margin,
+            h + 2 * margin,
+        );
+        let scale = SVG_SIZE / view.2.max(view.3) as f64;
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.0}" height="{:.0}" viewBox="{} {} {} {}">"#,
+            view.2 as f64 * scale,
+            view.3 as f64 * scale,
+            view.0,
+            view.1,
+            view.2,
+            view.3
+        )?;
+
+        let mut points: Vec<Point> = self.ships.iter().map(|s| s.pos).collect();
+        points.dedup();
+        let points: Vec<_> = points.iter().map(|p| format!("{},{}", p.x, -p.y)).collect();
+        writeln!(
+            out,
+            r#"  <polyline points="{}" fill="none" stroke="black" stroke-width="1" vector-effect="non-scaling-stroke"/>"#,
+            points.join(" ")
+        )?;
+
+        let radius = margin as f64 / 2.0;
+        let (farthest, _) = self.max_distance();
+        for (ship, colour) in [
+            (&self.ships[0], "green"),
+            (self.end(), "red"),
+            (&self.ships[farthest], "blue"),
+        ] {
+            writeln!(
+                out,
+                r#"  <circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                ship.pos.x, -ship.pos.y, radius, colour
+            )?;
+        }
+
+        writeln!(out, "</svg>")
+    }
+}
+
+/// The longer side of the SVG, in pixels.
+const SVG_SIZE: f64 = 800.0;
+
+impl Bounds {
+    pub fn width(&self) -> u32 {
+        self.min.x.abs_diff(self.max.x)
+    }
+
+    pub fn height(&self) -> u32 {
+        self.min.y.abs_diff(self.max.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Res;
+
+    const EXAMPLE: &str = "F10\nN3\nF7\nR90\nF11\n";
+
+    #[test]
+    fn record() -> Res<()> {
+        let actions = Action::read_actions(EXAMPLE.as_bytes())?;
+
+        let heading = Trajectory::record(&actions, Mode::Heading);
+        assert_eq!(heading.ships.len(), 6);
+        assert_eq!(heading.end().pos, Point { x: 17, y: -8 });
+        assert_eq!(heading.max_distance(), (5, 25));
+        assert_eq!(
+            heading.bounds(),
+            Bounds {
+                min: Point { x: 0, y: -8 },
+                max: Point { x: 17, y: 3 },
+            }
+        );
+
+        let waypoint = Trajectory::record(&actions, Mode::Waypoint);
+        assert_eq!(waypoint.end().pos, Point { x: 214, y: -72 });
+        assert_eq!(waypoint.end().waypoint, Point { x: 4, y: -10 });
+        assert_eq!(waypoint.max_distance(), (5, 286));
+        assert_eq!(
+            (waypoint.bounds().width(), waypoint.bounds().height()),
+            (214, 110)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn zero_actions() -> Res<()> {
+        // Step i is the ship after line i, even when a line does nothing.
+        let actions = Action::read_actions("F10\nN0\nR0\nF7\n".as_bytes())?;
+        let heading = Trajectory::record(&actions, Mode::Heading);
+        assert_eq!(heading.ships.len(), 5);
+        assert_eq!(heading.ships[3].pos, Point { x: 10, y: 0 });
+        assert_eq!(heading.max_distance(), (4, 17));
+        Ok(())
+    }
+
+    #[test]
+    fn svg() -> Res<()> {
+        let actions = Action::read_actions(EXAMPLE.as_bytes())?;
+        let mut svg = vec![];
+        Trajectory::record(&actions, Mode::Heading).write_svg(&mut svg)?;
+        let svg = String::from_utf8(svg).unwrap();
+
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="800" height="547" viewBox="-1 -4 19 13">"#
+        ));
+        // The turn doesn't move the ship, so it doesn't add a point.
+        assert!(svg.contains(r#"points="0,0 10,0 10,-3 17,-3 17,8""#));
+        assert!(svg.contains(r#"<circle cx="17" cy="8" r="0.5" fill="red"/>"#));
+        assert!(svg.ends_with("</svg>\n"));
+        Ok(())
+    }
+}
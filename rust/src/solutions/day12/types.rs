@@ -1,4 +1,4 @@
-use crate::Res;
+use crate::{Context, Error, Res};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use std::io::prelude::*;
@@ -6,7 +6,7 @@ use std::ops::{AddAssign, Mul};
 use Action::{Forward, Rotate, Translate};
 use Direction::{E, N, S, W};
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -34,7 +34,7 @@ impl Point {
     }
 
     pub fn manhattan_norm(self) -> u32 {
-        self.x.abs() as u32 + self.y.abs() as u32
+        self.x.unsigned_abs() + self.y.unsigned_abs()
     }
 }
 
@@ -56,7 +56,7 @@ impl Mul<u32> for Point {
     }
 }
 
-#[derive(Clone, Copy, FromPrimitive)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive)]
 pub enum Direction {
     N,
     E,
@@ -84,7 +84,7 @@ impl Direction {
 
 /// Counter-clockwise rotation by a multiple of 90 degrees.
 #[allow(non_camel_case_types)]
-#[derive(Clone, Copy, FromPrimitive)]
+#[derive(Debug, Clone, Copy, FromPrimitive)]
 pub enum Rotation {
     CCW_0,
     CCW_90,
@@ -95,16 +95,17 @@ pub enum Rotation {
 impl Rotation {
     fn new(c: char, n: u32) -> Res<Rotation> {
         if !(c == 'L' || c == 'R') {
-            return Err(format!("Invalid character: {}", c).into());
+            return Err(Error::invalid_char(c).at_col(1));
         }
         if n % 90 != 0 {
-            return Err(format!("Invalid number of degrees for rotation: {}", n).into());
+            let msg = format!("Invalid number of degrees for rotation: {}", n);
+            return Err(Error::invalid(msg).at_col(2));
         }
 
         let mut num_times_ccw = n / 90 % 4;
         if c == 'R' {
             // Flip by 180 degrees.
-            num_times_ccw = 4 - num_times_ccw;
+            num_times_ccw = (4 - num_times_ccw) % 4;
         }
 
         Ok(Rotation::from_u32(num_times_ccw).unwrap())
@@ -115,7 +116,7 @@ impl Rotation {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
 pub enum Action {
     Forward(u32),
     Translate(Direction, u32),
@@ -125,9 +126,9 @@ pub enum Action {
 impl Action {
     fn new(c: char, n: u32) -> Res<Action> {
         if c == 'F' {
-            return Ok(Forward(n));
+            Ok(Forward(n))
         } else if let Some(d) = Direction::new(c) {
-            return Ok(Translate(d, n));
+            Ok(Translate(d, n))
         } else {
             Ok(Rotate(Rotation::new(c, n)?))
         }
@@ -135,17 +136,16 @@ impl Action {
 
     pub fn read_actions(input: impl BufRead) -> Res<Vec<Action>> {
         let mut actions = vec![];
-        for line in input.lines() {
+        for (i, line) in input.lines().enumerate() {
             let line = line?;
             let mut chars = line.chars();
             let c = match chars.next() {
                 Some(c) => c,
-                None => return Err("Empty line in input".into()),
+                None => return Err(Error::malformed("Empty line in input").at_line(i + 1)),
             };
-            let n: u32 = chars.collect::<String>().parse()?;
-            if n != 0 {
-                actions.push(Action::new(c, n)?);
-            }
+            let n: u32 = chars.collect::<String>().parse().at(i + 1, 2)?;
+            // Keep no-op actions like `F0`, so that action `i` is always on line `i + 1`.
+            actions.push(Action::new(c, n).at_line(i + 1)?);
         }
         Ok(actions)
     }
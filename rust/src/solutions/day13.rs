@@ -1,18 +1,32 @@
-use crate::Res;
-use std::io::{self, prelude::*};
+use crate::{Context, Error, Res, Solution};
+use std::io::prelude::*;
 
-pub fn main() -> Res<()> {
-    let input = read_input(io::stdin().lock())?;
+pub struct Day13;
 
-    let Part1Ret { bus_id, wait_time } =
-        part1(input.start_time, &input.bus_ids()).expect("bus_ids is empty");
-    println!("{}", bus_id * wait_time);
-    println!("{}", part2(&input.constraints));
-    Ok(())
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+
+    type Input = Input;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Input> {
+        read_input(input)
+    }
+
+    fn part1(input: &Input) -> Res<u32> {
+        let Part1Ret { bus_id, wait_time } =
+            part1(input.start_time, &input.bus_ids()).ok_or("bus_ids is empty")?;
+        Ok(bus_id * wait_time)
+    }
+
+    fn part2(input: &Input) -> Res<u64> {
+        Ok(part2(&input.constraints))
+    }
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     start_time: u32,
     constraints: Vec<Constraint>,
 }
@@ -32,27 +46,29 @@ impl Input {
 fn read_input(input: impl BufRead) -> Res<Input> {
     let mut lines = input.lines();
     let start_time = match lines.next() {
-        Some(line) => line?.parse()?,
-        None => return Err("Empty input".into()),
+        Some(line) => line?.parse().at_line(1)?,
+        None => return Err(Error::eof("the start time")),
     };
 
     let line = match lines.next() {
         Some(line) => line?,
-        None => return Err("Expected 2 lines, got only 1".into()),
+        None => return Err(Error::eof("the bus IDs").at_line(2)),
     };
     if lines.next().is_some() {
-        return Err("Too many lines in input; expected only 2".into());
+        return Err(Error::malformed("Too many lines in input; expected only 2").at_line(3));
     }
 
     let mut constraints = vec![];
+    let mut col = 1;
     for (i, word) in line.split(',').enumerate() {
         if word != "x" {
             let c = Constraint {
-                bus_id: word.parse()?,
+                bus_id: word.parse().at(2, col)?,
                 offset: i as u32,
             };
             constraints.push(c);
         }
+        col += word.chars().count() + 1;
     }
     Ok(Input {
         start_time,
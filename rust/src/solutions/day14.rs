@@ -1,14 +1,29 @@
-use crate::Res;
+use crate::{Context, Error, Res, Solution};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
-use std::io::{self, prelude::*};
+use std::io::prelude::*;
 
-pub fn main() -> Res<()> {
-    let program = read_input(io::stdin().lock())?;
-    println!("{}", run(&program, Version::V1));
-    println!("{}", run(&program, Version::V2));
-    Ok(())
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+
+    type Input = Vec<Statement>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<Statement>> {
+        read_input(input)
+    }
+
+    fn part1(program: &Vec<Statement>) -> Res<u64> {
+        Ok(run(program, Version::V1))
+    }
+
+    fn part2(program: &Vec<Statement>) -> Res<u64> {
+        Ok(run(program, Version::V2))
+    }
 }
 
 fn run(program: &[Statement], version: Version) -> u64 {
@@ -104,8 +119,8 @@ impl Mask {
 
 pub fn read_input(input: impl BufRead) -> Res<Vec<Statement>> {
     let mut program = vec![];
-    for line in input.lines() {
-        program.push(Statement::new(&line?)?);
+    for (i, line) in input.lines().enumerate() {
+        program.push(Statement::new(&line?).at_line(i + 1)?);
     }
     Ok(program)
 }
@@ -117,15 +132,16 @@ impl Statement {
             static ref ASSN_RE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
         }
 
-        if let Some(caps) = MASK_RE.captures(&line) {
+        if let Some(caps) = MASK_RE.captures(line) {
             Ok(Statement::Mask(Mask::new(&caps[1])))
-        } else if let Some(caps) = ASSN_RE.captures(&line) {
+        } else if let Some(caps) = ASSN_RE.captures(line) {
             Ok(Statement::Assign {
                 addr: caps[1].parse()?,
                 val: caps[2].parse()?,
             })
         } else {
-            Err(format!("Line did not match any statement: {}", line).into())
+            let msg = format!("Line did not match any statement: {}", line);
+            Err(Error::malformed(msg))
         }
     }
 }
@@ -156,12 +172,11 @@ impl Mask {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
-    use std::io::BufReader;
+    use crate::open_input;
 
     #[test]
     fn part1() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/14")?);
+        let input = open_input(14)?;
         let program = read_input(input)?;
         assert_eq!(run(&program, Version::V1), 4886706177792);
         Ok(())
@@ -169,7 +184,7 @@ mod tests {
 
     #[test]
     fn part2() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/14")?);
+        let input = open_input(14)?;
         let program = read_input(input)?;
         assert_eq!(run(&program, Version::V2), 3348493585827);
         Ok(())
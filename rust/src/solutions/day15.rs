@@ -1,10 +1,43 @@
-use crate::Res;
+use crate::{Context, Error, Res, Solution};
 use std::collections::HashMap;
+use std::io::prelude::*;
 
-pub fn main() -> Res<()> {
-    println!("{}", memory_game(&[8, 11, 0, 19, 1, 2], 2020));
-    println!("{}", memory_game(&[8, 11, 0, 19, 1, 2], 30_000_000));
-    Ok(())
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<u32>> {
+        read_input(input)
+    }
+
+    fn part1(prefix: &Vec<u32>) -> Res<u32> {
+        Ok(memory_game(prefix, 2020))
+    }
+
+    fn part2(prefix: &Vec<u32>) -> Res<u32> {
+        Ok(memory_game(prefix, 30_000_000))
+    }
+}
+
+/// The input is a single line of comma-separated starting numbers, e.g. `8,11,0,19,1,2`.
+fn read_input(input: impl BufRead) -> Res<Vec<u32>> {
+    let line = match input.lines().next() {
+        Some(line) => line?,
+        None => return Err(Error::eof("a list of starting numbers")),
+    };
+
+    let mut prefix = vec![];
+    let mut col = 1;
+    for word in line.split(',') {
+        prefix.push(word.trim().parse().at(1, col)?);
+        col += word.chars().count() + 1;
+    }
+    Ok(prefix)
 }
 
 fn memory_game(prefix: &[u32], n: usize) -> u32 {
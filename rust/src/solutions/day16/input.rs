@@ -1,18 +1,18 @@
-use crate::Res;
+use crate::{Context, Error, Res};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
-use std::io::prelude::*;
+use std::io::{self, prelude::*};
 use std::ops::Range;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Input {
     pub constraints: HashMap<String, Constraint>,
     pub my_ticket: Vec<u32>,
     pub other_tickets: Vec<Vec<u32>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Constraint {
     pub include: Range<u32>,
     pub exclude: Range<u32>,
@@ -24,19 +24,27 @@ impl Constraint {
     }
 }
 
+/// A line of input, numbered from 1.
+type Line = (usize, io::Result<String>);
+
 impl Input {
-    pub fn read(mut input: impl BufRead) -> Res<Input> {
-        let constraints = Self::read_constraints(&mut input)?;
+    pub fn read(input: impl BufRead) -> Res<Input> {
+        // Number the lines from 1, for error messages.
+        let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        let constraints = Self::read_constraints(&mut lines)?;
 
-        Self::check_line(&mut input, "your ticket:")?;
-        let my_ticket = Self::read_ticket(&mut input)?;
-        Self::check_line(&mut input, "")?;
+        Self::check_line(&mut lines, "your ticket:")?;
+        let my_ticket = match lines.next() {
+            Some((n, line)) => Self::parse_ticket(&line?).at_line(n)?,
+            None => return Err(Error::eof("your ticket")),
+        };
+        Self::check_line(&mut lines, "")?;
 
-        Self::check_line(&mut input, "nearby tickets:")?;
+        Self::check_line(&mut lines, "nearby tickets:")?;
         let mut other_tickets = vec![];
-        // While there are more lines:
-        while !input.fill_buf()?.is_empty() {
-            other_tickets.push(Self::read_ticket(&mut input)?);
+        for (n, line) in lines {
+            other_tickets.push(Self::parse_ticket(&line?).at_line(n)?);
         }
 
         Ok(Input {
@@ -47,50 +55,52 @@ impl Input {
     }
 
     /// Consumes a line and checks it against `expected`.
-    fn check_line(input: &mut impl BufRead, expected: &str) -> Res<()> {
-        match input.lines().next() {
-            Some(line) => {
+    fn check_line(lines: &mut impl Iterator<Item = Line>, expected: &str) -> Res<()> {
+        match lines.next() {
+            Some((n, line)) => {
                 let line = line?;
                 if line == expected {
                     Ok(())
                 } else {
-                    Err(format!("Bad input: line did not match '{}': {}", expected, line).into())
+                    let msg = format!("line did not match '{}': {}", expected, line);
+                    Err(Error::malformed(msg).at_line(n))
                 }
             }
-            None => Err(format!("Unexpected EOF when expecting '{}'", expected).into()),
+            None => Err(Error::eof(format!("'{}'", expected))),
         }
     }
 
-    /// Read a line and parse it as a comma-separated list of one or more numbers.
-    fn read_ticket(input: &mut impl BufRead) -> Res<Vec<u32>> {
-        let line = match input.lines().next() {
-            Some(line) => line?,
-            None => return Err("Unexpected EOF when reading ticket".into()),
-        };
+    /// Parse a comma-separated list of one or more numbers.
+    fn parse_ticket(line: &str) -> Res<Vec<u32>> {
         let mut vals = vec![];
+        let mut col = 1;
         for word in line.split(',') {
-            vals.push(word.parse()?);
+            vals.push(word.parse().at_col(col)?);
+            col += word.chars().count() + 1;
         }
         Ok(vals)
     }
 
     /// Consumes up to and including the first empty line.
-    fn read_constraints(input: &mut impl BufRead) -> Res<HashMap<String, Constraint>> {
+    fn read_constraints(
+        lines: &mut impl Iterator<Item = Line>,
+    ) -> Res<HashMap<String, Constraint>> {
         let mut contraints = HashMap::new();
-        for line in input.lines() {
+        for (n, line) in lines {
             let line = line?;
-            if line == "" {
+            if line.is_empty() {
                 return Ok(contraints);
             }
 
-            let (name, constr) = Self::parse_constraint(&line)?;
+            let (name, constr) = Self::parse_constraint(&line).at_line(n)?;
             let ret = contraints.insert(String::from(name), constr);
             if ret.is_some() {
-                return Err(format!("Field defined twice: {}", name).into());
+                let msg = format!("Field defined twice: {}", name);
+                return Err(Error::invalid(msg).at(n, 1));
             }
         }
 
-        Err("Bad input: expected empty line but didn't find one".into())
+        Err(Error::eof("an empty line after the fields"))
     }
 
     fn parse_constraint(line: &str) -> Res<(&str, Constraint)> {
@@ -98,9 +108,12 @@ impl Input {
             static ref RE: Regex = Regex::new(r"^(.+): (\d+)-(\d+) or (\d+)-(\d+)$").unwrap();
         }
 
-        let caps = match RE.captures(&line) {
+        let caps = match RE.captures(line) {
             Some(caps) => caps,
-            None => return Err(format!("Line doesn't match constraint regex: {}", line).into()),
+            None => {
+                let msg = format!("expected e.g. `class: 1-3 or 5-7`, got `{}`", line);
+                return Err(Error::malformed(msg));
+            }
         };
 
         let ll: u32 = caps[2].parse()?;
@@ -108,7 +121,8 @@ impl Input {
         let r: u32 = caps[4].parse()?;
         let rr: u32 = caps[5].parse()?;
         if !(ll <= l && l <= r && r <= rr) {
-            return Err(format!("Bounds not increasing: {} {} {} {}", ll, l, r, rr).into());
+            let msg = format!("Bounds not increasing: {} {} {} {}", ll, l, r, rr);
+            return Err(Error::invalid(msg));
         }
 
         let constr = Constraint {
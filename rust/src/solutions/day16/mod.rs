@@ -1,16 +1,31 @@
-use crate::Res;
+use crate::{Res, Solution};
 use input::{Constraint, Input};
 use std::collections::HashMap;
-use std::io;
+use std::io::BufRead;
 use std::mem;
 
 mod input;
 
-pub fn main() -> Res<()> {
-    let input = Input::read(io::stdin().lock())?;
-    println!("{}", input.error_rate());
-    println!("{}", input.part2());
-    Ok(())
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+
+    type Input = Input;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Input> {
+        Input::read(input)
+    }
+
+    fn part1(input: &Input) -> Res<u32> {
+        Ok(input.error_rate())
+    }
+
+    fn part2(input: &Input) -> Res<u64> {
+        Ok(input.clone().part2())
+    }
 }
 
 impl Input {
@@ -54,7 +69,7 @@ impl Input {
     }
 
     fn remove_invalid_tickets(&mut self) {
-        let tickets = mem::replace(&mut self.other_tickets, vec![]);
+        let tickets = mem::take(&mut self.other_tickets);
 
         self.other_tickets = tickets
             .into_iter()
@@ -1,16 +1,31 @@
-use crate::Res;
+use crate::{Error, Res, Solution};
 use point::{Point, Point3, Point4};
 use std::collections::HashSet;
-use std::io::{self, prelude::*};
+use std::io::prelude::*;
 use std::iter;
 
 mod point;
 
-pub fn main() -> Res<()> {
-    let coords = read_input(io::stdin().lock())?;
-    println!("{}", State::<Point3>::new(&coords).simulate(6).num_active());
-    println!("{}", State::<Point4>::new(&coords).simulate(6).num_active());
-    Ok(())
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+
+    type Input = Vec<(i32, i32)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<(i32, i32)>> {
+        read_input(input)
+    }
+
+    fn part1(coords: &Vec<(i32, i32)>) -> Res<usize> {
+        Ok(State::<Point3>::new(coords).simulate(6).num_active())
+    }
+
+    fn part2(coords: &Vec<(i32, i32)>) -> Res<usize> {
+        Ok(State::<Point4>::new(coords).simulate(6).num_active())
+    }
 }
 
 fn read_input(input: impl BufRead) -> Res<Vec<(i32, i32)>> {
@@ -20,7 +35,7 @@ fn read_input(input: impl BufRead) -> Res<Vec<(i32, i32)>> {
             match c {
                 '#' => coords.push((x as i32, y as i32)),
                 '.' => (),
-                _ => return Err(format!("Invalid char: {}", c).into()),
+                _ => return Err(Error::invalid_char(c).at(y + 1, x + 1)),
             }
         }
     }
@@ -61,11 +76,10 @@ impl<P: Point> State<P> {
     }
 
     fn is_active_next_round(&self, p: P) -> bool {
-        match (self.active.contains(&p), self.num_active_neighbours(p)) {
-            (true, 2..=3) => true,
-            (false, 3) => true,
-            _ => false,
-        }
+        matches!(
+            (self.active.contains(&p), self.num_active_neighbours(p)),
+            (true, 2..=3) | (false, 3)
+        )
     }
 
     fn num_active_neighbours(&self, p: P) -> usize {
@@ -1,157 +1,229 @@
-use crate::Res;
-use std::io::{self, prelude::*};
-use std::ops::{Add, Mul};
-
-pub fn main() -> Res<()> {
-    let mut soln = Solution::new(io::stdin().lock())?;
-    println!("{}", soln.part1());
-    println!("{}", soln.part2());
-    Ok(())
+use crate::{col_of, Context, Error, Res, Solution};
+use std::fmt;
+use std::io::prelude::*;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+
+    type Input = Homework;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Homework> {
+        Homework::new(input)
+    }
+
+    fn part1(homework: &Homework) -> Res<u64> {
+        homework.part1()
+    }
+
+    fn part2(homework: &Homework) -> Res<u64> {
+        homework.part2()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: Kind,
+    col: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Token {
+enum Kind {
     Num(u64),
     Op(char),
     LeftParen,
     RightParen,
 }
 
-fn tokenize(expr: &str) -> Vec<Token> {
+/// Split a line into tokens, checking that they make an expression: numbers and operators take
+/// turns, and brackets match. So `Homework::eval` can't fail, except by overflowing.
+fn tokenize(expr: &str) -> Res<Vec<Token>> {
     let mut tokens = vec![];
+    let mut expect_num = true;
+    // Columns of the brackets that are still open.
+    let mut open = vec![];
+
     let mut i = 0;
     while i < expr.len() {
-        let c = expr.as_bytes()[i] as char;
-        match c {
+        let c = expr[i..].chars().next().unwrap();
+        let col = col_of(expr, i);
+        let kind = match c {
             ' ' => {
                 // Ignore spaces.
                 i += 1;
+                continue;
             }
             '*' | '+' => {
-                tokens.push(Token::Op(c));
                 i += 1;
+                Kind::Op(c)
             }
             '(' => {
-                tokens.push(Token::LeftParen);
                 i += 1;
+                Kind::LeftParen
             }
             ')' => {
-                tokens.push(Token::RightParen);
                 i += 1;
+                Kind::RightParen
             }
             '0'..='9' => {
                 // Parse the entire number.
                 let mut j = i + 1;
-                while j < expr.len() && matches!(expr.as_bytes()[j], b'0'..=b'9') {
+                while j < expr.len() && expr.as_bytes()[j].is_ascii_digit() {
                     j += 1;
                 }
-                let n: u64 = expr[i..j].parse().unwrap();
-                tokens.push(Token::Num(n));
+                let n: u64 = expr[i..j].parse().at_col(col)?;
                 i = j;
+                Kind::Num(n)
+            }
+            _ => return Err(Error::invalid_char(c).at_col(col)),
+        };
+
+        match kind {
+            Kind::Num(_) | Kind::LeftParen if !expect_num => {
+                let msg = format!("Expected an operator, got {}", kind);
+                return Err(Error::malformed(msg).at_col(col));
+            }
+            Kind::Op(_) | Kind::RightParen if expect_num => {
+                let msg = format!("Expected a number, got {}", kind);
+                return Err(Error::malformed(msg).at_col(col));
             }
-            _ => panic!("Invalid char: {}", c),
+            Kind::Num(_) => expect_num = false,
+            Kind::Op(_) => expect_num = true,
+            Kind::LeftParen => open.push(col),
+            Kind::RightParen => {
+                if open.pop().is_none() {
+                    return Err(Error::malformed("Spurious ')'").at_col(col));
+                }
+            }
+        }
+        tokens.push(Token { kind, col });
+    }
+
+    let end_col = col_of(expr, expr.len());
+    if expect_num {
+        return Err(Error::eof("a number").at_col(end_col));
+    }
+    if let Some(&col) = open.last() {
+        return Err(Error::malformed("No matching ')'").at_col(col));
+    }
+    Ok(tokens)
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kind::Num(n) => write!(f, "{}", n),
+            Kind::Op(c) => write!(f, "'{}'", c),
+            Kind::LeftParen => write!(f, "'('"),
+            Kind::RightParen => write!(f, "')'"),
         }
     }
-    tokens
 }
 
+/// The index of the `)` that closes the `(` at `lparen_idx`. `tokenize` checks that there is one.
 fn find_matching_rparen(tokens: &[Token], lparen_idx: usize) -> usize {
-    let mut depth = 1;
-    for (i, &token) in tokens.iter().enumerate().skip(lparen_idx + 1) {
-        match token {
-            Token::LeftParen => depth += 1,
-            Token::RightParen => depth -= 1,
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(lparen_idx) {
+        match token.kind {
+            Kind::LeftParen => depth += 1,
+            Kind::RightParen => depth -= 1,
             _ => (),
         }
         if depth == 0 {
             return i;
         }
     }
-    panic!("No matching rparen")
+    unreachable!("unmatched '(' at column {}", tokens[lparen_idx].col)
 }
 
-struct Solution {
+/// The list of expressions to evaluate.
+pub struct Homework {
     lines: Vec<Vec<Token>>,
-    add_before_mul: bool,
 }
 
-impl Solution {
-    fn new(input: impl BufRead) -> Res<Solution> {
+impl Homework {
+    fn new(input: impl BufRead) -> Res<Homework> {
         let mut lines = vec![];
-        for line in input.lines() {
-            lines.push(tokenize(&line?));
+        for (i, line) in input.lines().enumerate() {
+            lines.push(tokenize(&line?).at_line(i + 1)?);
         }
-        Ok(Solution {
-            lines,
-            add_before_mul: false,
-        })
+        Ok(Homework { lines })
     }
 
-    fn part1(&mut self) -> u64 {
-        self.add_before_mul = false;
-        self.eval_all()
+    fn part1(&self) -> Res<u64> {
+        self.eval_all(false)
     }
 
-    fn part2(&mut self) -> u64 {
-        self.add_before_mul = true;
-        self.eval_all()
+    fn part2(&self) -> Res<u64> {
+        self.eval_all(true)
     }
 
     /// Returns the sum of all lines after evaluating each.
-    fn eval_all(&self) -> u64 {
-        self.lines.iter().map(|l| self.eval(l)).sum()
+    fn eval_all(&self, add_before_mul: bool) -> Res<u64> {
+        let mut total: u64 = 0;
+        for (i, line) in self.lines.iter().enumerate() {
+            let n = Self::eval(line, add_before_mul).at_line(i + 1)?;
+            total = total
+                .checked_add(n)
+                .ok_or_else(|| Error::invalid("Overflow adding up the lines"))?;
+        }
+        Ok(total)
     }
 
-    fn eval(&self, tokens: &[Token]) -> u64 {
+    fn eval(tokens: &[Token], add_before_mul: bool) -> Res<u64> {
         let mut nums = vec![];
+        // Each operator, with its column.
         let mut ops = vec![];
-        let mut expect_num = true;
 
         let mut i = 0;
         while i < tokens.len() {
-            match tokens[i] {
-                Token::LeftParen => {
+            match tokens[i].kind {
+                Kind::LeftParen => {
                     let j = find_matching_rparen(tokens, i);
-                    nums.push(self.eval(&tokens[i + 1..j]));
+                    nums.push(Self::eval(&tokens[i + 1..j], add_before_mul)?);
                     i = j + 1;
                 }
-                Token::RightParen => panic!("Spurrious rparen"),
-                Token::Num(n) => {
-                    assert!(expect_num);
+                Kind::Num(n) => {
                     nums.push(n);
                     i += 1;
                 }
-                Token::Op(c) => {
-                    assert!(!expect_num);
-                    ops.push(c);
+                Kind::Op(c) => {
+                    ops.push((c, tokens[i].col));
                     i += 1;
                 }
+                Kind::RightParen => unreachable!("unmatched ')' at column {}", tokens[i].col),
             }
-            expect_num = !expect_num;
         }
-        assert!(!expect_num);
         debug_assert_eq!(nums.len(), ops.len() + 1);
 
-        fold_ops(&nums, &ops, self.add_before_mul)
+        fold_ops(&nums, &ops, add_before_mul)
     }
 }
 
-fn fold_ops(nums: &[u64], ops: &[char], add_before_mul: bool) -> u64 {
+/// Apply an operator, with an error at its column if the result doesn't fit in a u64.
+fn apply((op, col): (char, usize), a: u64, b: u64) -> Res<u64> {
+    let f = match op {
+        '*' => u64::checked_mul,
+        '+' => u64::checked_add,
+        _ => unreachable!(),
+    };
+    f(a, b).ok_or_else(|| Error::invalid(format!("Overflow: {} {} {}", a, op, b)).at_col(col))
+}
+
+fn fold_ops(nums: &[u64], ops: &[(char, usize)], add_before_mul: bool) -> Res<u64> {
     if add_before_mul {
-        let (nums, ops) = first_pass(&nums, &ops);
+        let (nums, ops) = first_pass(nums, ops)?;
         return fold_ops(&nums, &ops, false);
     }
 
     let mut acc = nums[0];
     for (i, &op) in ops.iter().enumerate() {
-        let f = match op {
-            '*' => u64::mul,
-            '+' => u64::add,
-            _ => unreachable!(),
-        };
-        acc = f(acc, nums[i + 1]);
+        acc = apply(op, acc, nums[i + 1])?;
     }
-    acc
+    Ok(acc)
 }
 
 /// Helper for fold_ops. Preprocess to perform the adds but not the muls.
@@ -160,24 +232,80 @@ fn fold_ops(nums: &[u64], ops: &[char], add_before_mul: bool) -> u64 {
 ///     1 + 2 * 3 + 4 * 5 + 6
 /// becomes
 ///     3 * 7 * 11
-fn first_pass(nums: &[u64], ops: &[char]) -> (Vec<u64>, Vec<char>) {
+#[allow(clippy::type_complexity)]
+fn first_pass(nums: &[u64], ops: &[(char, usize)]) -> Res<(Vec<u64>, Vec<(char, usize)>)> {
     let mut new_nums = vec![];
     let mut new_ops = vec![];
 
     new_nums.push(nums[0]);
     for (i, &op) in ops.iter().enumerate() {
-        match op {
+        match op.0 {
             '*' => {
                 new_ops.push(op);
                 new_nums.push(nums[i + 1]);
             }
             '+' => {
-                let len = new_nums.len();
-                new_nums[len - 1] += nums[i + 1];
+                let last = new_nums.last_mut().unwrap();
+                *last = apply(op, *last, nums[i + 1])?;
             }
             _ => unreachable!(),
         }
     }
 
-    (new_nums, new_ops)
+    Ok((new_nums, new_ops))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(line: &str, add_before_mul: bool) -> Res<u64> {
+        Homework::eval(&tokenize(line)?, add_before_mul)
+    }
+
+    #[test]
+    fn examples() -> Res<()> {
+        assert_eq!(eval("1 + (2 * 3) + (4 * (5 + 6))", false)?, 51);
+        assert_eq!(
+            eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", false)?,
+            13632
+        );
+        assert_eq!(
+            eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", true)?,
+            23340
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        let err = |line: &str| tokenize(line).unwrap_err().to_string();
+        assert_eq!(
+            err("1 + (2 * 3"),
+            "column 5: malformed line: No matching ')'"
+        );
+        assert_eq!(err("1 + 2) * 3"), "column 6: malformed line: Spurious ')'");
+        assert_eq!(
+            err("1 (2)"),
+            "column 3: malformed line: Expected an operator, got '('"
+        );
+        assert_eq!(
+            err("1 + * 2"),
+            "column 5: malformed line: Expected a number, got '*'"
+        );
+        assert_eq!(
+            err("()"),
+            "column 2: malformed line: Expected a number, got ')'"
+        );
+        assert_eq!(
+            err("1 +"),
+            "column 4: unexpected end of input, expected a number"
+        );
+
+        let e = eval("2 + (99999999999 * 99999999999)", false).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "column 18: Overflow: 99999999999 * 99999999999"
+        );
+    }
 }
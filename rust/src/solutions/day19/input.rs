@@ -1,13 +1,16 @@
+use crate::{Error, Res};
+
 /// Split the input on the first blank line, to get a list of rules and a list of queries.
-///
-/// Panics if there's no blank line.
-pub fn read_input(lines: &[String]) -> Input {
-    let blank = first_blank_line(&lines).expect("No blank line");
+pub fn read_input(lines: &[String]) -> Res<Input<'_>> {
+    let blank = match first_blank_line(lines) {
+        Some(i) => i,
+        None => return Err(Error::eof("a blank line after the rules")),
+    };
 
     let rules = &lines[..blank];
     let queries = &lines[blank + 1..];
 
-    Input { rules, queries }
+    Ok(Input { rules, queries })
 }
 
 /// Return value of `read_input`.
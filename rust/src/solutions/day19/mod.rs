@@ -1,35 +1,54 @@
-use crate::Res;
-use std::io::{self, prelude::*};
-use std::collections::{HashSet, HashMap};
+use crate::{Error, Res, Solution};
 use input::{read_input, Input};
-use pattern::{Label, Pattern, Node};
+use pattern::{Label, Node, Pattern};
+use std::collections::{HashMap, HashSet};
+use std::io::prelude::*;
 
 mod input;
 mod pattern;
 
-pub fn main() -> Res<()> {
-    let lines: Vec<_> = io::stdin().lock().lines().collect::<Result<_, _>>()?;
-    let input = read_input(&lines);
-    let soln = Solution::new(&input);
+pub struct Day19;
 
-    println!("{}", soln.part1());
-    println!("{}", soln.part2());
+impl Solution for Day19 {
+    const DAY: u32 = 19;
 
-    Ok(())
+    type Input = Validator;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Validator> {
+        let lines: Vec<_> = input.lines().collect::<Result<_, _>>()?;
+        let input = read_input(&lines)?;
+        Validator::new(&input)
+    }
+
+    fn part1(validator: &Validator) -> Res<usize> {
+        Ok(validator.part1())
+    }
+
+    fn part2(validator: &Validator) -> Res<usize> {
+        validator.part2()
+    }
 }
 
-struct Solution<'a> {
-    input: &'a Input<'a>,
+/// The pattern, along with the accepted set of each of its rules, and the queries to check.
+pub struct Validator {
+    queries: Vec<String>,
     pattern: Pattern,
     accepted_sets: HashMap<Label, HashSet<String>>,
 }
 
-impl<'a> Solution<'a> {
-    fn new(input: &'a Input<'a>) -> Self {
-        let pattern = Pattern::new(input.rules.iter().map(String::as_str));
+impl Validator {
+    fn new(input: &Input) -> Res<Self> {
+        let pattern = Pattern::new(input.rules.iter().map(String::as_str))?;
         let accepted_sets = pattern.compute_accepted_sets();
+        let queries = input.queries.to_vec();
 
-        Self { input, pattern, accepted_sets }
+        Ok(Self {
+            queries,
+            pattern,
+            accepted_sets,
+        })
     }
 
     fn part1(&self) -> usize {
@@ -37,35 +56,53 @@ impl<'a> Solution<'a> {
         self.num_valid(|s| accepted.contains(s))
     }
 
-    fn part2(&self) -> usize {
-        self.assert_part2_assumptions();
-        self.num_valid(|s| self.accepted_part2(s))
+    fn part2(&self) -> Res<usize> {
+        self.check_part2_assumptions()?;
+        Ok(self.num_valid(|s| self.accepted_part2(s)))
     }
 
     fn num_valid(&self, mut is_valid: impl FnMut(&str) -> bool) -> usize {
-        self.input.queries.iter().filter(|&s| is_valid(s)).count()
+        self.queries.iter().filter(|&s| is_valid(s)).count()
     }
 
-    fn assert_part2_assumptions(&self) {
-        assert_eq!(self.pattern.nodes[&self.pattern.root], Node::Branch { groups: vec![vec![Label(8), Label(11)]] });
-        assert_eq!(self.pattern.nodes[&Label(8)], Node::Branch { groups: vec![vec![Label(42)]] });
-        assert_eq!(self.pattern.nodes[&Label(11)], Node::Branch { groups: vec![vec![Label(42), Label(31)]] });
+    fn check_part2_assumptions(&self) -> Res<()> {
+        let expect_rule = |label: Label, groups: Vec<Vec<Label>>| {
+            if self.pattern.nodes.get(&label) == Some(&Node::Branch { groups }) {
+                Ok(())
+            } else {
+                Err(Error::invalid(format!(
+                    "Part 2 expects a different rule {}",
+                    label.0
+                )))
+            }
+        };
+        expect_rule(self.pattern.root, vec![vec![Label(8), Label(11)]])?;
+        expect_rule(Label(8), vec![vec![Label(42)]])?;
+        expect_rule(Label(11), vec![vec![Label(42), Label(31)]])?;
 
         // We'll use the fact that rules 42 and 31 have no overlap in what they accept.
         let a1 = &self.accepted_sets[&Label(42)];
         let a2 = &self.accepted_sets[&Label(31)];
-        let both = a1.intersection(a2);
-        assert_eq!(both.count(), 0);
+        if a1.intersection(a2).next().is_some() {
+            return Err(Error::invalid(
+                "Part 2 expects rules 42 and 31 to accept disjoint sets",
+            ));
+        }
 
         // We'll also use the following fact:
         // All the strings accepted by 42 or 31 have the same length.
         let str_lens: HashSet<_> = a1.union(a2).map(String::len).collect();
-        assert_eq!(str_lens.len(), 1);
+        if str_lens.len() != 1 {
+            return Err(Error::invalid(
+                "Part 2 expects rules 42 and 31 to accept strings of a single length",
+            ));
+        }
+        Ok(())
     }
 
     /// Is `s` accepted according to the changes described in part 2?
     ///
-    /// This assumes the inputs are valid, according to `assert_part2_assumptions`.
+    /// This assumes the inputs are valid, according to `check_part2_assumptions`.
     fn accepted_part2(&self, s: &str) -> bool {
         // Conceptually, we are performing the following replacements:
         //  8: 42      ==>    8: 42    | 42 8
@@ -84,7 +121,7 @@ impl<'a> Solution<'a> {
 
         // Special cases.
         if match_len == 0 {
-            return s == "";
+            return s.is_empty();
         }
         if s.len() % match_len != 0 {
             return false;
@@ -118,7 +155,12 @@ impl<'a> Solution<'a> {
     /// Helper function for `accepted_part2`.
     ///
     /// Find where to split the input string, based on the first match of 31.
-    fn expected_num_42s(s: &str, a2: &HashSet<String>, match_len: usize, n: usize) -> Option<usize> {
+    fn expected_num_42s(
+        s: &str,
+        a2: &HashSet<String>,
+        match_len: usize,
+        n: usize,
+    ) -> Option<usize> {
         (0..n).find(|&idx| {
             let i = idx * match_len;
             let j = (idx + 1) * match_len;
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
-mod new;
 mod compute_accepted;
+mod new;
 
 /// A pattern, represented as a tree of rules.
 pub struct Pattern {
@@ -1,6 +1,6 @@
+use super::{Label, Node, Pattern};
 use std::collections::{HashMap, HashSet};
 use std::iter;
-use super::{Label, Pattern, Node};
 
 impl Pattern {
     /// Compute the accepted sets of each label. Likely pretty expensive.
@@ -23,7 +23,7 @@ impl Pattern {
             Node::Branch { groups } => {
                 let accepted_set_of_each_group: Vec<HashSet<String>> = groups
                     .iter()
-                    .map(|g| cartesian_product(&self.accepted_set_of_each_label(&g, memo)))
+                    .map(|g| cartesian_product(&self.accepted_set_of_each_label(g, memo)))
                     .collect();
 
                 union(accepted_set_of_each_group)
@@ -87,8 +87,8 @@ fn union(sets: Vec<HashSet<String>>) -> HashSet<String> {
     let mut acc = HashSet::with_capacity(len);
 
     for set in sets {
-        acc.extend(set.into_iter());
+        acc.extend(set);
     }
 
     acc
-}
\ No newline at end of file
+}
@@ -1,22 +1,42 @@
+use super::{Label, Node, Pattern};
+use crate::{Context, Error, Res};
 use std::collections::HashMap;
-use super::{Label, Pattern, Node};
 
 impl Pattern {
-    /// Create a new pattern tree from a list of rules.
+    /// Create a new pattern tree from a list of rules, one per line.
     ///
     /// We don't check for cycles, we just assume there are none.
-    pub fn new<'a>(rules: impl Iterator<Item=&'a str>) -> Self {
+    pub fn new<'a>(rules: impl Iterator<Item = &'a str>) -> Res<Self> {
         let mut nodes = HashMap::new();
 
-        for rule in rules {
-            let (label, node) = parse_rule(rule);
+        for (i, rule) in rules.enumerate() {
+            let (label, node) = parse_rule(rule).at_line(i + 1)?;
             let ret = nodes.insert(label, node);
-            assert!(ret.is_none(), "Label defined twice: {:?}", label);
+            if ret.is_some() {
+                return Err(
+                    Error::invalid(format!("Label defined twice: {}", label.0)).at(i + 1, 1)
+                );
+            }
         }
 
-        assert!(nodes.contains_key(&Label(0)), "Root label 0 never defined.");
+        if !nodes.contains_key(&Label(0)) {
+            return Err(Error::invalid("Root label 0 never defined"));
+        }
+        for node in nodes.values() {
+            if let Node::Branch { groups } = node {
+                if let Some(l) = groups.iter().flatten().find(|l| !nodes.contains_key(l)) {
+                    return Err(Error::invalid(format!(
+                        "Label {} used but never defined",
+                        l.0
+                    )));
+                }
+            }
+        }
 
-        Self { root: Label(0), nodes }
+        Ok(Self {
+            root: Label(0),
+            nodes,
+        })
     }
 }
 
@@ -27,12 +47,18 @@ impl Pattern {
 ///
 /// Leaves look like this:
 /// 32: "a"
-fn parse_rule(rule: &str) -> (Label, Node) {
-    let mut halves = rule.split(": ");
-
-    let label: u32 = halves.next().unwrap().parse().unwrap();
-    let rest = halves.next().unwrap();
-    assert!(halves.next().is_none());
+fn parse_rule(rule: &str) -> Res<(Label, Node)> {
+    let (label, rest) = match rule.split_once(": ") {
+        Some(halves) => halves,
+        None => {
+            return Err(Error::malformed(format!(
+                "expected e.g. `1: 2 3 | 4`, got `{}`",
+                rule
+            )))
+        }
+    };
+    let rest_col = label.chars().count() + 3;
+    let label: u32 = label.parse().at_col(1)?;
 
     // We assume the only possible leaves are "a" and "b".
     let node = if rest == "\"a\"" {
@@ -44,14 +70,14 @@ fn parse_rule(rule: &str) -> (Label, Node) {
             .split(" | ")
             .map(|g| {
                 g.split_whitespace()
-                    .map(|n| n.parse::<u32>().unwrap())
-                    .map(|n| Label(n))
+                    .map(|n| n.parse::<u32>().at_col(rest_col))
+                    .map(|n| n.map(Label))
                     .collect()
             })
-            .collect();
+            .collect::<Res<_>>()?;
 
         Node::Branch { groups }
     };
 
-    (Label(label), node)
+    Ok((Label(label), node))
 }
@@ -0,0 +1,99 @@
+use crate::{col_of, Context, Error, Res, Solution};
+use policy::{Policy, Rule};
+use regex::Regex;
+use std::io::prelude::*;
+
+pub mod policy;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Row>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<Row>> {
+        read_input(input)
+    }
+
+    fn part1(input: &Vec<Row>) -> Res<usize> {
+        Ok(count_valid(input, &POLICY1.parse::<Rule>()?))
+    }
+
+    fn part2(input: &Vec<Row>) -> Res<usize> {
+        Ok(count_valid(input, &POLICY2.parse::<Rule>()?))
+    }
+}
+
+/// The sled rental place's policy.
+pub const POLICY1: &str = "count c in low-high";
+
+/// The Official Toboggan Corporate Policy. There's no position 0, and a position past the end
+/// of the password never matches.
+pub const POLICY2: &str = "at low is c xor at high is c";
+
+/// A line of the database: a policy's parameters, and a password.
+pub struct Row {
+    low: usize,
+    high: usize,
+    c: char,
+    password: String,
+}
+
+fn read_input<R: BufRead>(input: R) -> Res<Vec<Row>> {
+    let re = Regex::new(r"^(\d+)-(\d+) (.): (.+)$").unwrap();
+
+    let mut rows = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        let caps = re.captures(&line).ok_or_else(|| {
+            Error::malformed(format!("expected e.g. `1-3 a: abcde`, got `{}`", line)).at_line(i + 1)
+        })?;
+
+        let low: usize = caps[1].parse().at(i + 1, 1)?;
+        let high_col = col_of(&line, caps.get(2).unwrap().start());
+        let high: usize = caps[2].parse().at(i + 1, high_col)?;
+        let c = caps[3].chars().next().unwrap();
+        let password = String::from(&caps[4]);
+        rows.push(Row {
+            low,
+            high,
+            c,
+            password,
+        });
+    }
+    Ok(rows)
+}
+
+/// How many passwords in the database are valid, according to `policy`?
+pub fn count_valid<P: Policy + ?Sized>(input: &[Row], policy: &P) -> usize {
+    input.iter().filter(|r| policy.is_valid(r)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_input;
+
+    #[test]
+    fn part1() -> Res<()> {
+        let input = open_input(2)?;
+        let input = read_input(input)?;
+        assert_eq!(count_valid(&input, &POLICY1.parse::<Rule>()?), 416);
+
+        // Policies can be plain functions, too.
+        let policy1 = |r: &Row| (r.low..=r.high).contains(&r.password.matches(r.c).count());
+        assert_eq!(count_valid(&input, &policy1), 416);
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> Res<()> {
+        let input = open_input(2)?;
+        let input = read_input(input)?;
+        assert_eq!(count_valid(&input, &POLICY2.parse::<Rule>()?), 688);
+        Ok(())
+    }
+}
@@ -0,0 +1,560 @@
+//! Password policies, and a small language for writing new ones.
+//!
+//! A policy is an expression over the password, which can refer to the row's `low`, `high`, and
+//! `c`. E.g., the two official policies are:
+//! ```text
+//! count c in low-high
+//! at low is c xor at high is c
+//! ```
+//!
+//! The grammar, loosest-binding first:
+//! ```text
+//! policy := xor ("or" xor)*
+//! xor    := and ("xor" and)*
+//! and    := unary ("and" unary)*
+//! unary  := "not" unary | "(" policy ")" | atom
+//! atom   := "count" chars "in" range      -- how many of the password's chars are in the set
+//!         | "len" "in" range              -- the password's length, in chars
+//!         | "at" num "is" chars           -- the char at a position, counting from 1
+//!         | "matches" "/" regex "/"       -- anywhere in the password; `\/` for a slash
+//! range  := num "-" (num | "*")           -- inclusive; `*` means no upper limit
+//! num    := digits | "low" | "high"
+//! chars  := "c" | "'" char "'" | "[" char... "]"   -- `[...]` is any of the chars listed
+//!         | "digit" | "alpha" | "upper" | "lower" | "alnum" | "punct" | "space" | "any"
+//! ```
+//!
+//! Lengths and positions count Unicode scalar values (Rust `char`s), not bytes. The character
+//! classes are Unicode-aware too, e.g. `upper` includes 'É'.
+
+use super::Row;
+use crate::{Context, Error, Res};
+use regex::Regex;
+use std::str::FromStr;
+
+/// A rule that decides whether a row's password is valid.
+pub trait Policy {
+    fn is_valid(&self, row: &Row) -> bool;
+}
+
+/// Plain functions are policies too.
+impl<F: Fn(&Row) -> bool> Policy for F {
+    fn is_valid(&self, row: &Row) -> bool {
+        self(row)
+    }
+}
+
+/// A policy written in the policy language. See the module docs.
+#[derive(Debug, Clone)]
+pub enum Rule {
+    Count { chars: Chars, low: Num, high: Bound },
+    Len { low: Num, high: Bound },
+    At { pos: Num, chars: Chars },
+    Matches(Regex),
+    Not(Box<Rule>),
+    And(Box<Rule>, Box<Rule>),
+    Xor(Box<Rule>, Box<Rule>),
+    Or(Box<Rule>, Box<Rule>),
+}
+
+/// A number, which may come from the row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Num {
+    Lit(usize),
+    Low,
+    High,
+}
+
+/// The upper end of a range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    At(Num),
+    Unbounded,
+}
+
+/// A set of characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Chars {
+    /// The row's character.
+    RowChar,
+    Lit(char),
+    OneOf(Vec<Chars>),
+    Digit,
+    Alpha,
+    Upper,
+    Lower,
+    Alnum,
+    Punct,
+    Space,
+    Any,
+}
+
+impl Policy for Rule {
+    fn is_valid(&self, row: &Row) -> bool {
+        match self {
+            Rule::Count { chars, low, high } => {
+                let count = row
+                    .password
+                    .chars()
+                    .filter(|&x| chars.contains(x, row))
+                    .count();
+                in_range(count, *low, *high, row)
+            }
+            Rule::Len { low, high } => in_range(row.password.chars().count(), *low, *high, row),
+            Rule::At { pos, chars } => {
+                // Positions count from 1, so 0 is never in the password.
+                let pos = pos.eval(row);
+                pos > 0
+                    && row
+                        .password
+                        .chars()
+                        .nth(pos - 1)
+                        .is_some_and(|x| chars.contains(x, row))
+            }
+            Rule::Matches(re) => re.is_match(&row.password),
+            Rule::Not(a) => !a.is_valid(row),
+            Rule::And(a, b) => a.is_valid(row) && b.is_valid(row),
+            Rule::Xor(a, b) => a.is_valid(row) ^ b.is_valid(row),
+            Rule::Or(a, b) => a.is_valid(row) || b.is_valid(row),
+        }
+    }
+}
+
+fn in_range(n: usize, low: Num, high: Bound, row: &Row) -> bool {
+    let high_ok = match high {
+        Bound::At(high) => n <= high.eval(row),
+        Bound::Unbounded => true,
+    };
+    low.eval(row) <= n && high_ok
+}
+
+impl Num {
+    fn eval(self, row: &Row) -> usize {
+        match self {
+            Num::Lit(n) => n,
+            Num::Low => row.low,
+            Num::High => row.high,
+        }
+    }
+}
+
+impl Chars {
+    fn contains(&self, x: char, row: &Row) -> bool {
+        match self {
+            Chars::RowChar => x == row.c,
+            Chars::Lit(c) => x == *c,
+            Chars::OneOf(sets) => sets.iter().any(|s| s.contains(x, row)),
+            Chars::Digit => x.is_ascii_digit(),
+            Chars::Alpha => x.is_alphabetic(),
+            Chars::Upper => x.is_uppercase(),
+            Chars::Lower => x.is_lowercase(),
+            Chars::Alnum => x.is_alphanumeric(),
+            Chars::Punct => !x.is_alphanumeric() && !x.is_whitespace() && !x.is_control(),
+            Chars::Space => x.is_whitespace(),
+            Chars::Any => true,
+        }
+    }
+
+    fn class(name: &str) -> Option<Chars> {
+        let chars = match name {
+            "c" => Chars::RowChar,
+            "digit" => Chars::Digit,
+            "alpha" => Chars::Alpha,
+            "upper" => Chars::Upper,
+            "lower" => Chars::Lower,
+            "alnum" => Chars::Alnum,
+            "punct" => Chars::Punct,
+            "space" => Chars::Space,
+            "any" => Chars::Any,
+            _ => return None,
+        };
+        Some(chars)
+    }
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    /// Errors have the column where the policy went wrong.
+    fn from_str(s: &str) -> Res<Rule> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            pos: 0,
+            end_col: s.chars().count() + 1,
+        };
+        let rule = parser.policy()?;
+        match parser.peek() {
+            None => Ok(rule),
+            Some(t) => Err(bad_policy(format!("unexpected {}", t.kind)).at_col(t.col)),
+        }
+    }
+}
+
+/// Policy errors say so, to tell them apart from errors in the rows being checked.
+fn bad_policy(msg: impl Into<String>) -> Error {
+    Error::invalid(format!("bad policy: {}", msg.into()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Word(String),
+    Char(char),
+    /// The chars between brackets, e.g. `[+-*]`.
+    Set(Vec<char>),
+    Regex(String),
+    Dash,
+    Star,
+    LeftParen,
+    RightParen,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    col: usize,
+}
+
+/// Helper for `Rule::from_str`.
+fn tokenize(s: &str) -> Res<Vec<Token>> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let col = i + 1;
+        let c = chars[i];
+
+        // The chars up to the closing `close`, or an error at `col` if there isn't one.
+        let delimited = |close: char, what: &str| -> Res<(Vec<char>, usize)> {
+            let mut body = vec![];
+            let mut j = i + 1;
+            while j < chars.len() && chars[j] != close {
+                // Only regexes can contain their delimiter, escaped.
+                if close == '/' && chars[j] == '\\' && chars.get(j + 1) == Some(&'/') {
+                    j += 1;
+                }
+                body.push(chars[j]);
+                j += 1;
+            }
+            if j == chars.len() {
+                return Err(bad_policy(format!("unterminated {}", what)).at_col(col));
+            }
+            Ok((body, j + 1))
+        };
+
+        let kind = match c {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '-' => {
+                i += 1;
+                Kind::Dash
+            }
+            '*' => {
+                i += 1;
+                Kind::Star
+            }
+            '(' => {
+                i += 1;
+                Kind::LeftParen
+            }
+            ')' => {
+                i += 1;
+                Kind::RightParen
+            }
+            '\'' => {
+                let (body, next) = delimited('\'', "character")?;
+                if body.len() != 1 {
+                    return Err(
+                        bad_policy("expected a single character between quotes").at_col(col)
+                    );
+                }
+                i = next;
+                Kind::Char(body[0])
+            }
+            '[' => {
+                let (body, next) = delimited(']', "set")?;
+                i = next;
+                Kind::Set(body)
+            }
+            '/' => {
+                let (body, next) = delimited('/', "regex")?;
+                i = next;
+                Kind::Regex(body.into_iter().collect())
+            }
+            // Keywords and numbers are all ASCII.
+            _ if c.is_ascii_alphanumeric() => {
+                let mut word = String::new();
+                while i < chars.len() && chars[i].is_ascii_alphanumeric() {
+                    word.push(chars[i]);
+                    i += 1;
+                }
+                Kind::Word(word)
+            }
+            _ => return Err(bad_policy(format!("invalid character {:?}", c)).at_col(col)),
+        };
+        tokens.push(Token { kind, col });
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent, with one function per rule in the grammar.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+
+    /// For errors at the end of the input.
+    end_col: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self, expected: &str) -> Res<Token> {
+        match self.tokens.get(self.pos) {
+            Some(t) => {
+                self.pos += 1;
+                Ok(t.clone())
+            }
+            None => {
+                Err(bad_policy(format!("expected {}, got the end", expected)).at_col(self.end_col))
+            }
+        }
+    }
+
+    /// Consume the next token if it's the given keyword.
+    fn keyword(&mut self, word: &str) -> bool {
+        match self.peek() {
+            Some(Token {
+                kind: Kind::Word(w),
+                ..
+            }) if w == word => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect_keyword(&mut self, word: &str) -> Res<()> {
+        let t = self.next(&format!("`{}`", word))?;
+        match t.kind {
+            Kind::Word(w) if w == word => Ok(()),
+            other => Err(bad_policy(format!("expected `{}`, got {}", word, other)).at_col(t.col)),
+        }
+    }
+
+    fn policy(&mut self) -> Res<Rule> {
+        let mut rule = self.xor()?;
+        while self.keyword("or") {
+            rule = Rule::Or(Box::new(rule), Box::new(self.xor()?));
+        }
+        Ok(rule)
+    }
+
+    fn xor(&mut self) -> Res<Rule> {
+        let mut rule = self.and()?;
+        while self.keyword("xor") {
+            rule = Rule::Xor(Box::new(rule), Box::new(self.and()?));
+        }
+        Ok(rule)
+    }
+
+    fn and(&mut self) -> Res<Rule> {
+        let mut rule = self.unary()?;
+        while self.keyword("and") {
+            rule = Rule::And(Box::new(rule), Box::new(self.unary()?));
+        }
+        Ok(rule)
+    }
+
+    fn unary(&mut self) -> Res<Rule> {
+        if self.keyword("not") {
+            return Ok(Rule::Not(Box::new(self.unary()?)));
+        }
+
+        let t = self.next("a policy")?;
+        match t.kind {
+            Kind::LeftParen => {
+                let rule = self.policy()?;
+                let close = self.next("`)`")?;
+                match close.kind {
+                    Kind::RightParen => Ok(rule),
+                    other => {
+                        Err(bad_policy(format!("expected `)`, got {}", other)).at_col(close.col))
+                    }
+                }
+            }
+            Kind::Word(w) if w == "count" => {
+                let chars = self.chars()?;
+                self.expect_keyword("in")?;
+                let (low, high) = self.range()?;
+                Ok(Rule::Count { chars, low, high })
+            }
+            Kind::Word(w) if w == "len" => {
+                self.expect_keyword("in")?;
+                let (low, high) = self.range()?;
+                Ok(Rule::Len { low, high })
+            }
+            Kind::Word(w) if w == "at" => {
+                let pos = self.num()?;
+                self.expect_keyword("is")?;
+                let chars = self.chars()?;
+                Ok(Rule::At { pos, chars })
+            }
+            Kind::Word(w) if w == "matches" => {
+                let re = self.next("a regex")?;
+                match re.kind {
+                    Kind::Regex(re_str) => {
+                        let re = Regex::new(&re_str)
+                            .map_err(|e| bad_policy(format!("bad regex: {}", e)))
+                            .at_col(t.col)?;
+                        Ok(Rule::Matches(re))
+                    }
+                    other => {
+                        Err(bad_policy(format!("expected /regex/, got {}", other)).at_col(re.col))
+                    }
+                }
+            }
+            other => Err(bad_policy(format!("expected a policy, got {}", other)).at_col(t.col)),
+        }
+    }
+
+    fn range(&mut self) -> Res<(Num, Bound)> {
+        let low = self.num()?;
+        let dash = self.next("`-`")?;
+        if dash.kind != Kind::Dash {
+            return Err(bad_policy(format!("expected `-`, got {}", dash.kind)).at_col(dash.col));
+        }
+        if let Some(Token {
+            kind: Kind::Star, ..
+        }) = self.peek()
+        {
+            self.pos += 1;
+            return Ok((low, Bound::Unbounded));
+        }
+        Ok((low, Bound::At(self.num()?)))
+    }
+
+    fn num(&mut self) -> Res<Num> {
+        let t = self.next("a number")?;
+        match &t.kind {
+            Kind::Word(w) if w == "low" => Ok(Num::Low),
+            Kind::Word(w) if w == "high" => Ok(Num::High),
+            Kind::Word(w) if w.chars().all(|c| c.is_ascii_digit()) => {
+                let n = w
+                    .parse()
+                    .map_err(|e| bad_policy(format!("bad number: {}", e)));
+                Ok(Num::Lit(n.at_col(t.col)?))
+            }
+            other => Err(bad_policy(format!("expected a number, got {}", other)).at_col(t.col)),
+        }
+    }
+
+    fn chars(&mut self) -> Res<Chars> {
+        let t = self.next("characters")?;
+        match t.kind {
+            Kind::Char(c) => Ok(Chars::Lit(c)),
+            Kind::Set(set) => Ok(Chars::OneOf(set.into_iter().map(Chars::Lit).collect())),
+            Kind::Word(w) => Chars::class(&w).ok_or_else(|| {
+                bad_policy(format!("unknown character class `{}`", w)).at_col(t.col)
+            }),
+            other => Err(bad_policy(format!("expected characters, got {}", other)).at_col(t.col)),
+        }
+    }
+}
+
+/// How tokens are shown in error messages.
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Kind::Word(w) => write!(f, "`{}`", w),
+            Kind::Char(c) => write!(f, "'{}'", c),
+            Kind::Set(set) => write!(f, "[{}]", set.iter().collect::<String>()),
+            Kind::Regex(re) => write!(f, "/{}/", re),
+            Kind::Dash => write!(f, "`-`"),
+            Kind::Star => write!(f, "`*`"),
+            Kind::LeftParen => write!(f, "`(`"),
+            Kind::RightParen => write!(f, "`)`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(low: usize, high: usize, c: char, password: &str) -> Row {
+        Row {
+            low,
+            high,
+            c,
+            password: String::from(password),
+        }
+    }
+
+    fn check(policy: &str, row: &Row) -> bool {
+        policy.parse::<Rule>().unwrap().is_valid(row)
+    }
+
+    #[test]
+    fn unicode() {
+        // 'é' is 2 bytes, so byte indexing would look at the wrong char.
+        let r = row(2, 3, 'ß', "éßx");
+        assert!(check("at low is c xor at high is c", &r));
+        assert!(check("at 1 is 'é' and len in 3-3", &r));
+        assert!(check("count lower in 3-* and not count upper in 1-*", &r));
+        assert!(!check("at 4 is any or at 0 is any", &r));
+
+        // Only ASCII digits count, in policies and in passwords.
+        assert!(!check("count digit in 1-*", &row(1, 1, 'a', "²٣")));
+        assert_eq!(
+            "len in ²-3".parse::<Rule>().unwrap_err().to_string(),
+            "column 8: bad policy: invalid character '²'"
+        );
+    }
+
+    #[test]
+    fn corporate_rules() {
+        let policy = "len in 8-* and count digit in 1-* and count upper in 1-* \
+                      and count [!@#$%] in 1-* and not matches /(?i)password/";
+        assert!(check(policy, &row(1, 1, 'a', "Tobogg4n!")));
+        assert!(!check(policy, &row(1, 1, 'a', "MyPassword1!")));
+        assert!(!check(policy, &row(1, 1, 'a', "Sh0rt!")));
+
+        // `and` binds tighter than `xor`, which binds tighter than `or`.
+        assert!(check(
+            "at 1 is 'a' or at 1 is 'b' and at 2 is 'z'",
+            &row(1, 1, 'a', "ab")
+        ));
+        assert!(!check(
+            "(at 1 is 'a' or at 1 is 'b') and at 2 is 'z'",
+            &row(1, 1, 'a', "ab")
+        ));
+        assert!(check(r"matches /a\/b/", &row(1, 1, 'a', "a/b")));
+    }
+
+    #[test]
+    fn errors() {
+        let err = |policy: &str| policy.parse::<Rule>().unwrap_err().to_string();
+        assert_eq!(
+            err("count c in 1-"),
+            "column 14: bad policy: expected a number, got the end"
+        );
+        assert_eq!(
+            err("count vowel in 1-2"),
+            "column 7: bad policy: unknown character class `vowel`"
+        );
+        assert_eq!(
+            err("len in 1-2 #"),
+            "column 12: bad policy: invalid character '#'"
+        );
+        assert_eq!(err("at 1 is c c"), "column 11: bad policy: unexpected `c`");
+        assert_eq!(
+            err("len in 99999999999999999999-*"),
+            "column 8: bad policy: bad number: number too large to fit in target type"
+        );
+        assert!(err("matches /(/").starts_with("column 1: bad policy: bad regex"));
+    }
+}
@@ -1,23 +1,36 @@
-use crate::Res;
-use std::io::{self, prelude::*};
+use crate::{Res, Solution};
+use graph::{orient_tile, Graph};
+use image::{delete_sea_monsters, fuse_image};
 use input::read_input;
-use tile::{Tile, Pixel};
-use graph::{Graph, orient_tile};
-use image::{fuse_image, delete_sea_monsters};
+use std::io::prelude::*;
+use tile::{Pixel, Tile};
 
-mod input;
-mod tile;
 mod graph;
 mod image;
+mod input;
+mod tile;
+
+pub struct Day20;
 
-pub fn main() -> Res<()> {
-    let tiles = read_input(io::stdin().lock().lines())?;
-    let mut graph = Graph::new(tiles);
+impl Solution for Day20 {
+    const DAY: u32 = 20;
 
-    println!("{}", part1(&graph));
-    println!("{}", part2(&mut graph));
+    type Input = Graph;
+    type Part1 = u64;
+    type Part2 = usize;
 
-    Ok(())
+    fn parse(input: &mut dyn BufRead) -> Res<Graph> {
+        let tiles = read_input(input.lines())?;
+        Ok(Graph::new(tiles))
+    }
+
+    fn part1(graph: &Graph) -> Res<u64> {
+        Ok(part1(graph))
+    }
+
+    fn part2(graph: &Graph) -> Res<usize> {
+        Ok(part2(&mut graph.clone()))
+    }
 }
 
 /// Return the product of the ids of the four corners.
@@ -28,7 +41,7 @@ fn part1(graph: &Graph) -> u64 {
 
     for (&t, neighbors) in &graph.edges {
         let num_adj = neighbors.len();
-        assert!(2 <= num_adj && num_adj <= 4);
+        assert!((2..=4).contains(&num_adj));
 
         // Each corner is adjacent to exactly 2 tiles.
         if num_adj == 2 {
@@ -54,8 +67,10 @@ fn part2(graph: &mut Graph) -> usize {
 
     // Delete sea monsters and count water tiles.
     let image = delete_sea_monsters(&image);
-    let num_water_tiles = image.pixels.iter().map(|row| {
-        row.iter().filter(|&&p| p == Pixel::White).count()
-    }).sum();
+    let num_water_tiles = image
+        .pixels
+        .iter()
+        .map(|row| row.iter().filter(|&&p| p == Pixel::White).count())
+        .sum();
     num_water_tiles
 }
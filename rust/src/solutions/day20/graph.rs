@@ -1,12 +1,13 @@
+use super::tile::border::Direction::{self, Up};
+use super::tile::border::{Border, DIRS};
+use super::tile::{Tile, TileId};
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use super::tile::{TileId, Tile};
-use super::tile::border::Direction::{self, Up};
-use super::tile::border::{DIRS, Border};
 
 /// Representation of the puzzle-graph.
 ///
 /// An edge connects each pair of tiles that have matching borders.
+#[derive(Clone)]
 pub struct Graph {
     pub tiles: HashMap<TileId, Tile>,
     pub edges: HashMap<TileId, Vec<TileId>>,
@@ -19,7 +20,9 @@ impl Graph {
     /// This panics if that assumption fails.
     pub fn new(tiles: HashMap<TileId, Tile>) -> Graph {
         // Each of t's normalized borders points to t.
-        let border_tile_pairs = tiles.values().flat_map(|t| t.borders().map(move |b| (b.normalize(), t.id)));
+        let border_tile_pairs = tiles
+            .values()
+            .flat_map(|t| t.borders().map(move |b| (b.normalize(), t.id)));
         let border_to_tiles = pairs_to_hashmap(border_tile_pairs);
 
         // For each border, insert an edge between the two tiles that share that border.
@@ -34,7 +37,10 @@ impl Graph {
                     edges.entry(a).or_default().push(b);
                     edges.entry(b).or_default().push(a);
                 }
-                _ => panic!("More than 2 tiles share this border. {:?} {:?}", border, tiles),
+                _ => panic!(
+                    "More than 2 tiles share this border. {:?} {:?}",
+                    border, tiles
+                ),
             }
         }
 
@@ -73,7 +79,11 @@ impl Graph {
         }
 
         // The graph should be connected.
-        assert_eq!(seen.len(), n, "Graph is disconnected, or there's a bug in our code.");
+        assert_eq!(
+            seen.len(),
+            n,
+            "Graph is disconnected, or there's a bug in our code."
+        );
     }
 
     /// Get the neighbor of this tile in this direction.
@@ -82,7 +92,10 @@ impl Graph {
     pub fn get_neighbor(&self, id: TileId, dir: Direction) -> Option<TileId> {
         let border = self.tiles[&id].border(dir);
         let neighbors = &self.edges[&id];
-        neighbors.iter().find(|t| self.tiles[t].has_border(border)).copied()
+        neighbors
+            .iter()
+            .find(|t| self.tiles[t].has_border(border))
+            .copied()
     }
 
     /// How many tiles are in the graph?
@@ -98,14 +111,19 @@ impl Graph {
 
     /// Tile dimension in pixels, including border.
     pub fn tile_dimension(&self) -> usize {
-        self.tiles.values().next().expect("Empty graph.").pixels.len()
+        self.tiles
+            .values()
+            .next()
+            .expect("Empty graph.")
+            .pixels
+            .len()
     }
 }
 
 /// Helper for `Graph::new`.
-fn pairs_to_hashmap<A, B>(pairs: impl Iterator<Item=(A, B)>) -> HashMap<A, Vec<B>>
-    where
-        A: Eq + Hash
+fn pairs_to_hashmap<A, B>(pairs: impl Iterator<Item = (A, B)>) -> HashMap<A, Vec<B>>
+where
+    A: Eq + Hash,
 {
     let mut map: HashMap<A, Vec<B>> = HashMap::with_capacity(pairs.size_hint().0);
     for (a, b) in pairs {
@@ -120,7 +138,12 @@ fn pairs_to_hashmap<A, B>(pairs: impl Iterator<Item=(A, B)>) -> HashMap<A, Vec<B
 ///
 /// Panics if this isn't possible.
 fn orient_tile_border(tile: &mut Tile, dir: Direction, border: Border) {
-    assert!(tile.has_border(border), "Tile doesn't have border.\n{:?}\n{:?}", tile, border);
+    assert!(
+        tile.has_border(border),
+        "Tile doesn't have border.\n{:?}\n{:?}",
+        tile,
+        border
+    );
 
     orient_tile(tile, |t| t.border(dir) == border);
 }
@@ -132,14 +155,18 @@ fn orient_tile_border(tile: &mut Tile, dir: Direction, border: Border) {
 /// Panics if this is impossible.
 pub fn orient_tile(tile: &mut Tile, predicate: impl Fn(&Tile) -> bool) {
     for _ in 0..4 {
-        if predicate(tile) { return; }
+        if predicate(tile) {
+            return;
+        }
         tile.rotate_ccw();
     }
 
     tile.flip();
 
     for _ in 0..4 {
-        if predicate(tile) { return; }
+        if predicate(tile) {
+            return;
+        }
         tile.rotate_ccw();
     }
 
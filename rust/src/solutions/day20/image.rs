@@ -1,6 +1,6 @@
 use super::graph::Graph;
-use super::tile::{Tile, Pixel, TileId};
 use super::tile::border::Direction::{Down, Right};
+use super::tile::{Pixel, Tile, TileId};
 
 /// Fuse the image into one big virtual "tile", by stripping borders and gluing tiles together.
 ///
@@ -12,18 +12,16 @@ pub fn fuse_image(graph: &Graph) -> Tile {
     let grid = grid_of_tiles(graph);
 
     let grid_dim = grid.len();
-    assert_eq!(grid_dim, 12);
     let tile_dim_incl_border = graph.tile_dimension();
-    assert_eq!(tile_dim_incl_border, 10);
 
     let tile_dim = tile_dim_incl_border - 2;
     let image_dim = grid_dim * tile_dim;
 
     let mut image = vec![vec![Pixel::Black; image_dim]; image_dim];
 
-    for i in 0..grid_dim {
-        for j in 0..grid_dim {
-            let tile = &graph.tiles[&grid[i][j]];
+    for (i, row_of_tiles) in grid.iter().enumerate() {
+        for (j, id) in row_of_tiles.iter().enumerate() {
+            let tile = &graph.tiles[id];
 
             for k in 0..tile_dim {
                 for l in 0..tile_dim {
@@ -35,7 +33,10 @@ pub fn fuse_image(graph: &Graph) -> Tile {
         }
     }
 
-    Tile { id: TileId(0xDEADBEEF), pixels: image }
+    Tile {
+        id: TileId(0xDEADBEEF),
+        pixels: image,
+    }
 }
 
 /// Helper for `fuse_image`.
@@ -83,11 +84,16 @@ fn top_left_corner(graph: &Graph) -> TileId {
     let mut ret = None;
 
     for (&id, neighbors) in &graph.edges {
-        if neighbors.len() == 2 &&
-            graph.get_neighbor(id, Down).is_some() &&
-            graph.get_neighbor(id, Right).is_some()
+        if neighbors.len() == 2
+            && graph.get_neighbor(id, Down).is_some()
+            && graph.get_neighbor(id, Right).is_some()
         {
-            assert!(ret.is_none(), "Two top-left corners. (Perhaps tiles weren't oriented first?) {:?} {:?}", ret, id);
+            assert!(
+                ret.is_none(),
+                "Two top-left corners. (Perhaps tiles weren't oriented first?) {:?} {:?}",
+                ret,
+                id
+            );
             ret = Some(id);
         }
     }
@@ -106,8 +112,8 @@ fn top_left_corner(graph: &Graph) -> TileId {
 pub fn delete_sea_monsters(image: &Tile) -> Tile {
     let mut output = image.clone();
 
-    for i in 0..image.pixels.len() - SEA_MONSTER_HEIGHT {
-        for j in 0..image.pixels[i].len() - SEA_MONSTER_WIDTH {
+    for i in 0..=image.pixels.len() - SEA_MONSTER_HEIGHT {
+        for j in 0..=image.pixels[i].len() - SEA_MONSTER_WIDTH {
             if sea_monster_at(image, i, j) {
                 delete_sea_monster(&mut output, i, j);
             }
@@ -119,9 +125,9 @@ pub fn delete_sea_monsters(image: &Tile) -> Tile {
 
 /// Helper for `delete_sea_monsters`.
 fn sea_monster_at(image: &Tile, i: usize, j: usize) -> bool {
-    SEA_MONSTER_POINTS.iter().all(|&(di, dj)| {
-        image.pixels[i + di][j + dj] == Pixel::White
-    })
+    SEA_MONSTER_POINTS
+        .iter()
+        .all(|&(di, dj)| image.pixels[i + di][j + dj] == Pixel::White)
 }
 
 /// Helper for `delete_sea_monsters`.
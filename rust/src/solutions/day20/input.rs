@@ -1,36 +1,56 @@
-use crate::Res;
+use super::tile::border::Border;
+use super::tile::{Pixel, Tile, TileId};
+use crate::{Context, Error, Res};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
 use std::io;
 use std::iter;
-use super::tile::{Pixel, TileId, Tile};
 
 /// Read the input into a collection of Tiles keyed by TileId.
 ///
 /// All tiles will be square and have the same size.
-pub fn read_input(mut lines: impl Iterator<Item = io::Result<String>>) -> Res<HashMap<TileId, Tile>> {
+pub fn read_input(lines: impl Iterator<Item = io::Result<String>>) -> Res<HashMap<TileId, Tile>> {
     let mut tiles = HashMap::new();
     let mut tile_size = None;
 
-    while let Some(tile) = read_tile(&mut lines)? {
+    // Number the lines from 1, for error messages.
+    let mut lines = lines.enumerate().map(|(i, line)| (i + 1, line));
+
+    while let Some((n, tile)) = read_tile(&mut lines)? {
         // Check the tiles are all the same size.
         let curr_size = tile.pixels.len();
         match tile_size {
-            Some(size) => if curr_size != size {
-                return Err(format!("Different tile sizes: {} {} {:?}", size, curr_size, tile).into());
+            Some(size) => {
+                if curr_size != size {
+                    return Err(Error::invalid(format!(
+                        "Different tile sizes: {} {} {:?}",
+                        size, curr_size, tile
+                    ))
+                    .at_line(n));
+                }
             }
-            None => if curr_size < 3 {
-                // There should at least be a border and a single inner pixel.
-                return Err(format!("Tile size empty or unreasonably small: {}", curr_size).into());
-            } else {
-                tile_size = Some(curr_size);
+            None => {
+                if curr_size < 3 {
+                    // There should at least be a border and a single inner pixel.
+                    return Err(Error::invalid(format!(
+                        "Tile size empty or unreasonably small: {}",
+                        curr_size
+                    ))
+                    .at_line(n));
+                } else if curr_size > Border::MAX_PIXELS {
+                    return Err(
+                        Error::invalid(format!("Tile size too big: {}", curr_size)).at_line(n)
+                    );
+                } else {
+                    tile_size = Some(curr_size);
+                }
             }
         }
 
         let ret = tiles.insert(tile.id, tile);
         if let Some(other) = ret {
-            return Err(format!("Repeated tile id: {:?}", other.id).into());
+            return Err(Error::invalid(format!("Repeated tile id: {:?}", other.id)).at_line(n));
         }
     }
 
@@ -39,40 +59,55 @@ pub fn read_input(mut lines: impl Iterator<Item = io::Result<String>>) -> Res<Ha
 
 /// Consume a single tile (and the following blank line "separator") from the input stream.
 ///
-/// Returns Ok(None) on end-of-stream.
-fn read_tile(lines: &mut impl Iterator<Item = io::Result<String>>) -> Res<Option<Tile>> {
+/// Returns the tile along with the number of its first line, or Ok(None) on end-of-stream.
+fn read_tile(
+    lines: &mut impl Iterator<Item = (usize, io::Result<String>)>,
+) -> Res<Option<(usize, Tile)>> {
     lazy_static! {
         static ref ID_REGEX: Regex = Regex::new(r"^Tile (\d+):$").unwrap();
     }
 
-    let first_line = match lines.next() {
-        Some(line) => line?,
+    let (first_n, first_line) = match lines.next() {
+        Some((n, line)) => (n, line?),
         None => return Ok(None),
     };
 
     let id: u32 = match ID_REGEX.captures(&first_line) {
-        Some(caps) => caps[1].parse()?,
-        None => return Err(format!("Line didn't match tile id regex: {:?}", first_line).into()),
+        Some(caps) => caps[1].parse().at_line(first_n)?,
+        None => {
+            return Err(Error::malformed(format!(
+                "expected e.g. `Tile 1234:`, got {:?}",
+                first_line
+            ))
+            .at_line(first_n))
+        }
     };
 
     let mut pixels = vec![];
 
-    for line in lines.chain(iter::once(Ok(String::new()))) {
+    for (n, line) in lines.chain(iter::once((0, Ok(String::new())))) {
         let line = line?;
 
         // A blank line signals the end of the current tile.
-        if line == "" {
+        if line.is_empty() {
             let tile = Tile::new(id, pixels);
 
             let n = tile.pixels.len();
             if n > 0 && tile.pixels.iter().all(|row| row.len() == n) {
-                return Ok(Some(tile));
+                return Ok(Some((first_n, tile)));
             } else {
-                return Err(format!("Jagged or non-square tile: {:?}", tile).into());
+                return Err(
+                    Error::invalid(format!("Jagged or non-square tile: {:?}", tile))
+                        .at_line(first_n),
+                );
             }
         }
 
-        let row: Res<Vec<Pixel>> = line.chars().map(Pixel::new).collect();
+        let row: Res<Vec<Pixel>> = line
+            .chars()
+            .enumerate()
+            .map(|(j, c)| Pixel::new(c).at(n, j + 1))
+            .collect();
         pixels.push(row?);
     }
 
@@ -1,5 +1,5 @@
+use crate::{Error, Res};
 use std::fmt;
-use crate::Res;
 
 pub mod border;
 
@@ -60,8 +60,8 @@ struct Point(usize, usize);
 /// Helper for `Tile::rotate_ccw`.
 ///
 /// Rotate a point counter-clockwise in all four quadrants at once.
-fn four_way_swap(m: &mut Vec<Vec<Pixel>>, tl: Point, tr: Point, br: Point, bl: Point) {
-    fn get(matrix: &mut Vec<Vec<Pixel>>, p: Point) -> &mut Pixel {
+fn four_way_swap(m: &mut [Vec<Pixel>], tl: Point, tr: Point, br: Point, bl: Point) {
+    fn get(matrix: &mut [Vec<Pixel>], p: Point) -> &mut Pixel {
         &mut matrix[p.0][p.1]
     }
 
@@ -77,7 +77,7 @@ impl Pixel {
         match c {
             '.' => Ok(Pixel::Black),
             '#' => Ok(Pixel::White),
-            _ => Err(format!("Invalid pixel: {}", c).into()),
+            _ => Err(Error::invalid_char(c)),
         }
     }
 
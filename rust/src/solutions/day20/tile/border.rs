@@ -1,41 +1,51 @@
-use std::fmt;
-use std::cmp::min;
-use Direction::{Down, Up, Right, Left};
-use super::Pixel::{Black, White, self};
+use super::Pixel::{self, Black, White};
 use super::Tile;
+use std::cmp::min;
+use std::fmt;
+use Direction::{Down, Left, Right, Up};
 
-/// A bitmask representing the pattern of pixels on a border.
+/// A bitmask representing the pattern of pixels on a border, along with its length.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct Border(u32);
+pub struct Border {
+    bits: u64,
+    len: usize,
+}
 
 impl Border {
-    const NUM_PIXELS: usize = 10;
+    /// Borders longer than this don't fit in the bitmask.
+    pub const MAX_PIXELS: usize = 64;
 
-    fn new(pixels: impl Iterator<Item=Pixel>) -> Border {
+    fn new(pixels: impl Iterator<Item = Pixel>) -> Border {
         let mut acc = 0;
+        let mut len = 0;
         for p in pixels {
             acc <<= 1;
             acc |= match p {
                 Black => 0,
                 White => 1,
             };
+            len += 1;
         }
-        Border(acc)
+        assert!(len <= Border::MAX_PIXELS, "Border too long: {}", len);
+        Border { bits: acc, len }
     }
 
     fn flip(self) -> Border {
-        let original = self.0;
+        let original = self.bits;
 
         let mut flipped = 0;
-        for i in 0..Border::NUM_PIXELS {
-            let j = Border::NUM_PIXELS - 1 - i;
+        for i in 0..self.len {
+            let j = self.len - 1 - i;
 
             if original & (1 << i) != 0 {
                 flipped |= 1 << j;
             }
         }
 
-        Border(flipped)
+        Border {
+            bits: flipped,
+            len: self.len,
+        }
     }
 
     pub fn normalize(self) -> Border {
@@ -66,7 +76,7 @@ pub const DIRS: [Direction; 4] = [Up, Right, Down, Left];
 
 impl Tile {
     /// This tile's 4 borders, in any order.
-    pub fn borders(&self) -> impl Iterator<Item=Border> + '_ {
+    pub fn borders(&self) -> impl Iterator<Item = Border> + '_ {
         DIRS.iter().map(move |&d| self.border(d))
     }
 
@@ -95,6 +105,6 @@ impl Tile {
 
 impl fmt::Debug for Border {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:0width$b}", self.0, width=Border::NUM_PIXELS)
+        write!(f, "{:0width$b}", self.bits, width = self.len)
     }
 }
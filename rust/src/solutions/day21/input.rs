@@ -1,6 +1,6 @@
-use regex::Regex;
+use crate::{Error, Res};
 use lazy_static::lazy_static;
-use super::Res;
+use regex::Regex;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Ingredient<'a>(pub &'a str);
@@ -14,7 +14,7 @@ pub struct Food<'a> {
     pub allergens: Vec<Allergen<'a>>,
 }
 
-pub fn read_input(lines: &[String]) -> Res<Vec<Food>> {
+pub fn read_input(lines: &[String]) -> Res<Vec<Food<'_>>> {
     lazy_static! {
         static ref REGEX: Regex = Regex::new(r"^(.*) \(contains (.*)\)$").unwrap();
     }
@@ -22,12 +22,30 @@ pub fn read_input(lines: &[String]) -> Res<Vec<Food>> {
     let mut foods = Vec::with_capacity(lines.len());
 
     for (i, line) in lines.iter().enumerate() {
-        match REGEX.captures(&line) {
-            None => return Err(format!("Input line {:?} malformed: {:?}", i, line).into()),
+        match REGEX.captures(line) {
+            None => {
+                let msg = format!("expected e.g. `a b (contains x, y)`, got {:?}", line);
+                return Err(Error::malformed(msg).at_line(i + 1));
+            }
             Some(caps) => {
-                let ingredients: Vec<_> = caps.get(1).unwrap().as_str().split(' ').map(Ingredient).collect();
-                let allergens: Vec<_> = caps.get(2).unwrap().as_str().split(", ").map(Allergen).collect();
-                foods.push(Food { ingredients, allergens });
+                let ingredients: Vec<_> = caps
+                    .get(1)
+                    .unwrap()
+                    .as_str()
+                    .split(' ')
+                    .map(Ingredient)
+                    .collect();
+                let allergens: Vec<_> = caps
+                    .get(2)
+                    .unwrap()
+                    .as_str()
+                    .split(", ")
+                    .map(Allergen)
+                    .collect();
+                foods.push(Food {
+                    ingredients,
+                    allergens,
+                });
             }
         }
     }
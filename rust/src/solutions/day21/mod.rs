@@ -1,24 +1,43 @@
-use crate::Res;
-use std::io::{self, BufRead};
+use crate::{Res, Solution};
+use input::{read_input, Allergen, Food, Ingredient};
 use std::collections::{HashMap, HashSet};
-use input::{read_input, Food, Allergen, Ingredient};
+use std::io::BufRead;
 
 mod input;
 
-pub fn main() -> Res<()> {
-    let lines: Vec<_> = io::stdin().lock().lines().collect::<Result<_, _>>()?;
-    let foods = read_input(&lines)?;
+pub struct Day21;
 
-    let map = allergen_intersections(&foods);
+impl Solution for Day21 {
+    const DAY: u32 = 21;
 
-    let ans = part1(&foods, &map);
-    println!("{}", ans);
+    /// The foods borrow from the input lines, so we hang on to the lines themselves.
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = String;
 
-    let ans = part2(&map);
-    let ans: Vec<_> = ans.into_iter().map(|(_, Ingredient(name))| name).collect();
-    println!("{}", ans.join(","));
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<String>> {
+        let lines: Vec<_> = input.lines().collect::<Result<_, _>>()?;
 
-    Ok(())
+        // Check the input is well-formed up front.
+        read_input(&lines)?;
+
+        Ok(lines)
+    }
+
+    fn part1(lines: &Vec<String>) -> Res<usize> {
+        let foods = read_input(lines)?;
+        let map = allergen_intersections(&foods);
+        Ok(part1(&foods, &map))
+    }
+
+    fn part2(lines: &Vec<String>) -> Res<String> {
+        let foods = read_input(lines)?;
+        let map = allergen_intersections(&foods);
+
+        let ans = part2(&map);
+        let ans: Vec<_> = ans.into_iter().map(|(_, Ingredient(name))| name).collect();
+        Ok(ans.join(","))
+    }
 }
 
 /// Return type of `allergen_intersections`.
@@ -107,9 +126,12 @@ fn part2<'a>(map: &AllergenMap<'a>) -> Vec<(Allergen<'a>, Ingredient<'a>)> {
 /// Find an allergen which has exactly one candidate translation.
 ///
 /// Panics if this is impossible.
-fn get_allergen<'a, 'm>(map: &'m AllergenMap<'a>) -> (Allergen<'a>, Ingredient<'a>) {
+fn get_allergen<'a>(map: &AllergenMap<'a>) -> (Allergen<'a>, Ingredient<'a>) {
     // Find the "minimum" allergen.
-    let (allergen, ingredients) = map.iter().min_by_key(|(_, ingredients)| ingredients.len()).unwrap();
+    let (allergen, ingredients) = map
+        .iter()
+        .min_by_key(|(_, ingredients)| ingredients.len())
+        .unwrap();
 
     match ingredients.len() {
         0 => panic!("Impossible to solve. Current map: {:?}", map),
@@ -120,4 +142,4 @@ fn get_allergen<'a, 'm>(map: &'m AllergenMap<'a>) -> (Allergen<'a>, Ingredient<'
         // Our approach won't work. (There may or may not be a solution.)
         _ => panic!("Failed to solve. Current map: {:?}", map),
     }
-}
\ No newline at end of file
+}
@@ -1,34 +1,56 @@
+use crate::{Context, Error, Res};
 use std::collections::VecDeque;
 use std::io::{self, BufRead};
 
 pub type Deck = VecDeque<u32>;
 
-pub fn read_input(input: impl BufRead) -> (Deck, Deck) {
-    let mut lines = input.lines();
+pub fn read_input(input: impl BufRead) -> Res<(Deck, Deck)> {
+    // Number the lines from 1, for error messages.
+    let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
 
-    let line = lines.next().unwrap().unwrap();
-    assert_eq!(line, "Player 1:");
-    let d1 = read_deck(&mut lines);
+    check_line(&mut lines, "Player 1:")?;
+    let d1 = read_deck(&mut lines)?;
 
-    let line = lines.next().unwrap().unwrap();
-    assert_eq!(line, "Player 2:");
-    let d2 = read_deck(&mut lines);
+    check_line(&mut lines, "Player 2:")?;
+    let d2 = read_deck(&mut lines)?;
 
-    assert!(lines.next().is_none());
+    if let Some((n, _)) = lines.next() {
+        return Err(Error::malformed("Expected end of input after player 2's deck").at_line(n));
+    }
+
+    Ok((d1, d2))
+}
 
-    (d1, d2)
+/// A line of input, numbered from 1.
+type Line = (usize, io::Result<String>);
+
+/// Consumes a line and checks it against `expected`.
+fn check_line(lines: &mut impl Iterator<Item = Line>, expected: &str) -> Res<()> {
+    match lines.next() {
+        Some((n, line)) => {
+            let line = line?;
+            if line == expected {
+                Ok(())
+            } else {
+                Err(Error::malformed(format!("expected '{}', got '{}'", expected, line)).at_line(n))
+            }
+        }
+        None => Err(Error::eof(format!("'{}'", expected))),
+    }
 }
 
 /// Reads until the first blank line or EOF.
-fn read_deck(lines: &mut impl Iterator<Item=Result<String, io::Error>>) -> Deck {
+fn read_deck(lines: &mut impl Iterator<Item = Line>) -> Res<Deck> {
     let mut deck = VecDeque::new();
 
-    for line in lines {
-        let line = line.unwrap();
-        if line == "" { break; }
+    for (n, line) in lines {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
 
-        deck.push_back(line.parse().unwrap());
+        deck.push_back(line.parse().at(n, 1)?);
     }
 
-    deck
+    Ok(deck)
 }
@@ -1,10 +1,34 @@
-use std::collections::HashSet;
-use crate::Res;
-use std::io;
+use crate::{Res, Solution};
 use input::{read_input, Deck};
+use std::collections::HashSet;
+use std::io::BufRead;
 
 mod input;
 
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+
+    type Input = (Deck, Deck);
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &mut dyn BufRead) -> Res<(Deck, Deck)> {
+        read_input(input)
+    }
+
+    fn part1((d1, d2): &(Deck, Deck)) -> Res<u32> {
+        let final_deck = part1(d1.clone(), d2.clone());
+        Ok(score(&final_deck))
+    }
+
+    fn part2((d1, d2): &(Deck, Deck)) -> Res<u32> {
+        let (_player, deck) = part2(d1.clone(), d2.clone());
+        Ok(score(&deck))
+    }
+}
+
 /*
 - read input: two lists of numbers (stripping the two headers)
 - simulate the game, until someone wins
@@ -15,18 +39,6 @@ mod input;
     - push both to the bottom of the winner's deck
  */
 
-pub fn main() -> Res<()> {
-    let (d1, d2) = read_input(io::stdin().lock());
-
-    let final_deck = part1(d1.clone(), d2.clone());
-    println!("{}", score(&final_deck));
-
-    let (_player, deck) = part2(d1, d2);
-    println!("{}", score(&deck));
-
-    Ok(())
-}
-
 /// Simulate playing the game, until someone wins.
 ///
 /// Return the state of the winning deck at the time the game ends.
@@ -1,17 +1,36 @@
+use crate::{Error, Res};
 use std::io::prelude::*;
 
 /// Read input into a list of numbers.
 ///
-/// `input` should just be a single line of decimal digits; panics otherwise.
-pub fn read_input(input: impl BufRead) -> Vec<u32> {
+/// `input` should just be a single line of decimal digits, which are the labels 1 to n in some
+/// order, for some n of at least 5.
+pub fn read_input(input: impl BufRead) -> Res<Vec<u32>> {
     let mut lines = input.lines();
-    let line = lines.next().unwrap().unwrap();
-    assert!(lines.next().is_none());
+    let line = match lines.next() {
+        Some(line) => line?,
+        None => return Err(Error::eof("a line of cup labels")),
+    };
+    if lines.next().is_some() {
+        return Err(Error::malformed("Too many lines in input; expected only 1").at_line(2));
+    }
 
     let mut nums = Vec::with_capacity(line.len());
-    for c in line.chars() {
-        let digit = c.to_digit(10).unwrap();
+    for (i, c) in line.chars().enumerate() {
+        let digit = match c.to_digit(10) {
+            Some(d) => d,
+            None => return Err(Error::invalid_char(c).at(1, i + 1)),
+        };
         nums.push(digit);
     }
-    nums
+
+    // Each move picks up 3 cups, and needs somewhere else to put them.
+    let mut sorted = nums.clone();
+    sorted.sort_unstable();
+    if sorted.len() < 5 || !sorted.iter().copied().eq(1..=sorted.len() as u32) {
+        let msg = format!("Expected the labels 1 to n, for some n >= 5; got {}", line);
+        return Err(Error::invalid(msg).at_line(1));
+    }
+
+    Ok(nums)
 }
@@ -1,28 +1,40 @@
-use crate::Res;
-use std::io;
-use std::iter;
+use crate::{Res, Solution};
 use input::read_input;
 use std::collections::HashMap;
+use std::io::BufRead;
+use std::iter;
 
 mod input;
 
-pub fn main() -> Res<()> {
-    let mut cups = read_input(io::stdin().lock());
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+
+    type Input = Vec<u32>;
+    type Part1 = String;
+    type Part2 = u64;
 
-    let mut ring = Ring::new(&cups);
-    ring.simulate_game(100);
-    let ans = ring.to_vec();
-    let ans: String = ans[1..].iter().map(u32::to_string).collect();
-    println!("{}", ans);
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<u32>> {
+        read_input(input)
+    }
 
-    extend_cups(&mut cups, 1_000_000);
-    let mut ring = Ring::new(&cups);
-    ring.simulate_game(10_000_000);
-    let a = ring.next[&1];
-    let b = ring.next[&a];
-    println!("{}", a as u64 * b as u64);
+    fn part1(cups: &Vec<u32>) -> Res<String> {
+        let mut ring = Ring::new(cups);
+        ring.simulate_game(100);
+        let ans = ring.to_vec();
+        Ok(ans[1..].iter().map(u32::to_string).collect())
+    }
 
-    Ok(())
+    fn part2(cups: &Vec<u32>) -> Res<u64> {
+        let mut cups = cups.clone();
+        extend_cups(&mut cups, 1_000_000);
+        let mut ring = Ring::new(&cups);
+        ring.simulate_game(10_000_000);
+        let a = ring.next[&1];
+        let b = ring.next[&a];
+        Ok(a as u64 * b as u64)
+    }
 }
 
 fn extend_cups(cups: &mut Vec<u32>, largest_cup: u32) {
@@ -52,7 +64,11 @@ impl Ring {
 
         let largest_cup = *cups.iter().max().unwrap();
 
-        Self { next, curr: cups[0], largest_cup }
+        Self {
+            next,
+            curr: cups[0],
+            largest_cup,
+        }
     }
 
     fn simulate_game(&mut self, num_rounds: u32) {
@@ -62,7 +78,9 @@ impl Ring {
     }
 
     fn simulate_round(&mut self) {
-        let removed: Vec<_> = iter::repeat_with(|| self.pop_after(self.curr)).take(3).collect();
+        let removed: Vec<_> = iter::repeat_with(|| self.pop_after(self.curr))
+            .take(3)
+            .collect();
 
         let dest = self.get_dest();
 
@@ -1,4 +1,5 @@
 use super::point::Point;
+use crate::{Error, Res};
 
 #[derive(Debug, Copy, Clone)]
 pub enum Dir {
@@ -14,16 +15,16 @@ use Dir::*;
 pub const DIRS: [Dir; 6] = [E, W, NE, NW, SE, SW];
 
 impl Dir {
-    pub fn new(s: &str) -> Dir {
+    pub fn new(s: &str) -> Res<Dir> {
         use Dir::*;
         match s {
-            "e" => E,
-            "w" => W,
-            "ne" => NE,
-            "nw" => NW,
-            "se" => SE,
-            "sw" => SW,
-            _ => panic!("Invalid dir string: {}", s),
+            "e" => Ok(E),
+            "w" => Ok(W),
+            "ne" => Ok(NE),
+            "nw" => Ok(NW),
+            "se" => Ok(SE),
+            "sw" => Ok(SW),
+            _ => Err(Error::malformed(format!("Invalid direction: {}", s))),
         }
     }
 
@@ -1,31 +1,36 @@
-use std::io::prelude::*;
 use super::dir::Dir;
+use crate::{Context, Error, Res};
+use std::io::prelude::*;
 
-pub fn read_input(input: impl BufRead) -> Vec<Vec<Dir>> {
-    input.lines().map(|line| {
-        read_line(&line.unwrap())
-    }).collect()
+pub fn read_input(input: impl BufRead) -> Res<Vec<Vec<Dir>>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| read_line(&line?).at_line(i + 1))
+        .collect()
 }
 
-fn read_line(line: &str) -> Vec<Dir> {
+fn read_line(line: &str) -> Res<Vec<Dir>> {
     let mut dirs = Vec::with_capacity(line.len());
 
-    let mut i = 0;
-    while i < line.len() {
-        let first = &line[i..i + 1];
+    let mut chars = line.chars().enumerate();
+    while let Some((i, first)) = chars.next() {
+        let mut s = String::from(first);
 
-        let s = if first == "e" || first == "w" {
-            i += 1;
-            first
-        } else {
-            let both = &line[i..i + 2];
-            i += 2;
-            both
-        };
+        if first == 'n' || first == 's' {
+            match chars.next() {
+                Some((_, second)) => s.push(second),
+                None => {
+                    return Err(
+                        Error::malformed(format!("Incomplete direction: {}", first)).at_col(i + 1)
+                    )
+                }
+            }
+        }
 
-        dirs.push(Dir::new(s));
+        dirs.push(Dir::new(&s).at_col(i + 1)?);
     }
 
     dirs.shrink_to_fit();
-    dirs
+    Ok(dirs)
 }
@@ -1,23 +1,37 @@
-use std::io;
-use crate::Res;
+use crate::{Res, Solution};
+use dir::Dir;
 use input::read_input;
 use part_1::part_1;
 use part_2::part_2;
+use std::io::BufRead;
 
-mod input;
 mod dir;
-mod point;
+mod input;
 mod part_1;
 mod part_2;
+mod point;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
 
-pub fn main() -> Res<()> {
-    let input = read_input(io::stdin().lock());
+    type Input = Vec<Vec<Dir>>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let mut black_tiles = part_1(&input);
-    println!("{}", black_tiles.len());
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<Vec<Dir>>> {
+        read_input(input)
+    }
 
-    part_2(&mut black_tiles);
-    println!("{}", black_tiles.len());
+    fn part1(input: &Vec<Vec<Dir>>) -> Res<usize> {
+        Ok(part_1(input).len())
+    }
 
-    Ok(())
-}
\ No newline at end of file
+    /// Part 2 starts from where part 1 left off.
+    fn part2(input: &Vec<Vec<Dir>>) -> Res<usize> {
+        let mut black_tiles = part_1(input);
+        part_2(&mut black_tiles);
+        Ok(black_tiles.len())
+    }
+}
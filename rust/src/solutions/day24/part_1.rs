@@ -1,6 +1,6 @@
-use std::collections::HashSet;
 use super::dir::Dir;
 use super::point::Point;
+use std::collections::HashSet;
 
 pub fn part_1(input: &[Vec<Dir>]) -> HashSet<Point> {
     let mut black_tiles = HashSet::new();
@@ -22,7 +22,7 @@ fn follow_dirs(dirs: &[Dir]) -> Point {
     let mut p = Point::default();
 
     for d in dirs {
-        p = p + d.to_point();
+        p += d.to_point();
     }
 
     p
@@ -1,5 +1,5 @@
-use std::ops::{Add, AddAssign};
 use super::dir::DIRS;
+use std::ops::{Add, AddAssign};
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default)]
 pub struct Point {
@@ -8,7 +8,7 @@ pub struct Point {
 }
 
 impl Point {
-    pub fn neighbors(self) -> impl Iterator<Item=Point> {
+    pub fn neighbors(self) -> impl Iterator<Item = Point> {
         DIRS.iter().map(move |d| self + d.to_point())
     }
 }
@@ -1,11 +1,29 @@
+use super::MOD;
+use crate::{Context, Error, Res};
 use std::io::BufRead;
 
-pub fn read_input(input: impl BufRead) -> (u64, u64) {
+pub fn read_input(input: impl BufRead) -> Res<(u64, u64)> {
     let mut lines = input.lines();
 
-    let a = lines.next().unwrap().unwrap().parse().unwrap();
-    let b = lines.next().unwrap().unwrap().parse().unwrap();
-    assert!(lines.next().is_none());
+    let a = read_key(lines.next(), 1)?;
+    let b = read_key(lines.next(), 2)?;
+    if lines.next().is_some() {
+        return Err(Error::malformed("Too many lines in input; expected only 2").at_line(3));
+    }
 
-    (a, b)
+    Ok((a, b))
+}
+
+/// Parse line `n`, which should be a public key.
+fn read_key(line: Option<std::io::Result<String>>, n: usize) -> Res<u64> {
+    let line = match line {
+        Some(line) => line?,
+        None => return Err(Error::eof("a public key")),
+    };
+
+    let key = line.parse().at(n, 1)?;
+    if key >= MOD {
+        return Err(Error::invalid(format!("Public key {} isn't less than {}", key, MOD)).at(n, 1));
+    }
+    Ok(key)
 }
@@ -1,7 +1,7 @@
-use std::io;
-use lazy_static::lazy_static;
-use crate::Res;
+use crate::{Res, Solution};
 use input::read_input;
+use lazy_static::lazy_static;
+use std::io::BufRead;
 
 /*
 inputs are:
@@ -13,43 +13,49 @@ and we want:
 find a and b by trial-and-error, then compute 7^ab
 note that we'll want a fast way to compute 7^n
 
-this is all mod 2020_12_27
+this is all mod 20_201_227
  */
 
 mod input;
 
-pub fn main() -> Res<()> {
-    let (key1, key2) = read_input(io::stdin().lock());
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+
+    /// There's no puzzle on the last day; just a star for finishing all the others.
+    const HAS_PART2: bool = false;
+
+    type Input = (u64, u64);
+    type Part1 = u64;
+    type Part2 = u64;
 
-    let ans = part_1(key1, key2);
-    println!("{}", ans);
+    fn parse(input: &mut dyn BufRead) -> Res<(u64, u64)> {
+        read_input(input)
+    }
 
-    Ok(())
+    fn part1(&(key1, key2): &(u64, u64)) -> Res<u64> {
+        part_1(key1, key2).ok_or_else(|| "A public key isn't a power of 7".into())
+    }
 }
 
-fn part_1(key1: u64, key2: u64) -> u64 {
-    let a = log_7(key1).unwrap();
-    let b = log_7(key2).unwrap();
+fn part_1(key1: u64, key2: u64) -> Option<u64> {
+    let a = log_7(key1)?;
+    let b = log_7(key2)?;
 
     // Note: do _not_ wrap this (% MOD).
     let ab = a * b;
 
-    power_of_7(ab)
+    Some(power_of_7(ab))
 }
 
-const MOD: u64 = 2020_12_27; // ~20 M
+const MOD: u64 = 20_201_227; // ~20 M
 
 /// Find a value e such that n == 7^e % MOD.
 fn log_7(n: u64) -> Option<u64> {
     assert!(n < MOD);
 
-    for e in 0..MOD {
-        if power_of_7(e) == n {
-            return Some(e);
-        }
-    }
-
-    None
+    (0..MOD).find(|&e| power_of_7(e) == n)
 }
 
 /// Compute 7^n % MOD.
@@ -77,7 +83,7 @@ fn precompute_powers_of_7() -> [u64; 64] {
 
     table[0] = 7;
     for i in 1..64 {
-        table[i] = table[i-1].pow(2) % MOD;
+        table[i] = table[i - 1].pow(2) % MOD;
     }
 
     table
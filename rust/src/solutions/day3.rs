@@ -1,14 +1,30 @@
-use crate::Res;
-use std::io::{self, prelude::*};
+use crate::{Context, Error, Res, Solution};
+use std::io::prelude::*;
 
-pub fn main() -> Res<()> {
-    let grid = read_input(io::stdin().lock())?;
-    println!("{}", part1(&grid));
-    println!("{}", part2(&grid));
-    Ok(())
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+
+    type Input = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Grid> {
+        read_input(input)
+    }
+
+    fn part1(grid: &Grid) -> Res<usize> {
+        Ok(part1(grid))
+    }
+
+    fn part2(grid: &Grid) -> Res<usize> {
+        Ok(part2(grid))
+    }
 }
 
-struct Grid {
+/// Every row is non-empty, and they all have the same width.
+pub struct Grid {
     rows: Vec<Vec<Tile>>,
 }
 
@@ -19,10 +35,27 @@ enum Tile {
 }
 
 fn read_input<R: BufRead>(input: R) -> Res<Grid> {
-    let mut rows = vec![];
-    for line in input.lines() {
-        let row: Res<_> = line?.chars().map(Tile::new).collect();
-        rows.push(row?);
+    let mut rows: Vec<Vec<Tile>> = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let row: Vec<_> = line?
+            .chars()
+            .enumerate()
+            .map(|(j, c)| Tile::new(c).at(i + 1, j + 1))
+            .collect::<Res<_>>()?;
+        if row.is_empty() {
+            return Err(Error::malformed("empty row").at_line(i + 1));
+        }
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                let msg = format!(
+                    "expected {} tiles, like row 1, got {}",
+                    first.len(),
+                    row.len()
+                );
+                return Err(Error::malformed(msg).at_line(i + 1));
+            }
+        }
+        rows.push(row);
     }
     Ok(Grid { rows })
 }
@@ -32,7 +65,14 @@ impl Tile {
         match c {
             '.' => Ok(Tile::Empty),
             '#' => Ok(Tile::Tree),
-            _ => Err(format!("Invalid tile character: {}", c).into()),
+            _ => Err(Error::invalid_char(c)),
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::Tree => '#',
         }
     }
 }
@@ -54,46 +94,145 @@ fn part2(grid: &Grid) -> usize {
     ans.product()
 }
 
-/// This should be a positive rational number, in simplest terms.
-#[derive(Debug, Clone, Copy)]
-struct Slope {
+/// A non-negative rational number, which should be in simplest terms. Straight down is 0 right
+/// and 1 down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slope {
     down: usize,
     right: usize,
 }
 
-fn num_collisions(grid: &Grid, slope: Slope) -> usize {
-    assert_ne!(slope.down, 0);
+impl Slope {
+    /// `down` must be at least 1, or the toboggan would never leave the first row.
+    pub fn new(right: usize, down: usize) -> Res<Slope> {
+        if down == 0 {
+            return Err(Error::invalid("A slope must go down at least 1"));
+        }
+        Ok(Slope { down, right })
+    }
+
+    pub fn right(self) -> usize {
+        self.right
+    }
+
+    pub fn down(self) -> usize {
+        self.down
+    }
+
+    /// Is this in simplest terms? E.g., 2 right and 2 down is the same path as 1 right and 1
+    /// down, just with half the rows skipped.
+    pub fn is_reduced(self) -> bool {
+        gcd(self.right, self.down) == 1
+    }
+}
 
-    let mut collisions = 0;
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Grid {
+    /// The tile at row `y` and column `x`, where each row repeats forever to the right.
+    fn get(&self, y: usize, x: usize) -> Tile {
+        let row = &self.rows[y];
+        row[x % row.len()]
+    }
 
-    let mut y = 0;
-    let mut x = 0;
+    /// The positions visited going down the slope from the top-left, with `x` unwrapped.
+    fn path(&self, slope: Slope) -> impl Iterator<Item = (usize, usize)> {
+        (0..self.rows.len())
+            .step_by(slope.down)
+            .enumerate()
+            .map(move |(i, y)| (y, i * slope.right))
+    }
 
-    let height = grid.rows.len();
-    while y < height {
-        let width = grid.rows[y].len();
-        assert_ne!(width, 0);
+    /// Does the path visit the last row? Slopes that go down too far in one step stop early, and
+    /// e.g. one that's steeper than the whole grid only visits the starting square.
+    fn reaches_bottom(&self, slope: Slope) -> bool {
+        (self.rows.len().max(1) - 1) % slope.down == 0
+    }
+}
 
-        if grid.rows[y][x % width] == Tile::Tree {
-            collisions += 1;
+fn num_collisions(grid: &Grid, slope: Slope) -> usize {
+    grid.path(slope)
+        .filter(|&(y, x)| grid.get(y, x) == Tile::Tree)
+        .count()
+}
+
+/// How one slope fared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlopeReport {
+    pub slope: Slope,
+    pub trees: usize,
+
+    /// How many squares the toboggan visits, including the first.
+    pub squares: usize,
+}
+
+/// Try every reduced slope with at most `max_right` right and `max_down` down, ordered by `down`
+/// and then `right`.
+pub fn scan_slopes(grid: &Grid, max_right: usize, max_down: usize) -> Vec<SlopeReport> {
+    let mut reports = vec![];
+    for down in 1..=max_down {
+        for right in 0..=max_right {
+            let slope = Slope { right, down };
+            if slope.is_reduced() {
+                reports.push(SlopeReport {
+                    slope,
+                    trees: num_collisions(grid, slope),
+                    squares: grid.path(slope).count(),
+                });
+            }
         }
+    }
+    reports
+}
 
-        y += slope.down;
-        x += slope.right;
+/// Of the slopes from `scan_slopes` that reach the bottom row, the one that hits the fewest
+/// trees. Ties go to the first one scanned.
+pub fn best_slope(grid: &Grid, max_right: usize, max_down: usize) -> Option<SlopeReport> {
+    scan_slopes(grid, max_right, max_down)
+        .into_iter()
+        .filter(|r| grid.reaches_bottom(r.slope))
+        .min_by_key(|r| r.trees)
+}
+
+/// Draw the grid with the path marked: `O` for an open square the toboggan visits, and `X` for a
+/// tree it hits. The path wraps around, like the grid does.
+pub fn render(grid: &Grid, slope: Slope) -> String {
+    let mut lines: Vec<Vec<char>> = grid
+        .rows
+        .iter()
+        .map(|row| row.iter().map(|&t| t.to_char()).collect())
+        .collect();
+
+    for (y, x) in grid.path(slope) {
+        let x = x % lines[y].len();
+        lines[y][x] = match grid.get(y, x) {
+            Tile::Empty => 'O',
+            Tile::Tree => 'X',
+        };
     }
 
-    collisions
+    let mut out = String::new();
+    for line in lines {
+        out.extend(line);
+        out.push('\n');
+    }
+    out
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
-    use std::io::BufReader;
+    use crate::open_input;
 
     #[test]
     fn part1() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/3")?);
+        let input = open_input(3)?;
         let grid = read_input(input)?;
         assert_eq!(super::part1(&grid), 234);
         Ok(())
@@ -101,9 +240,60 @@ mod tests {
 
     #[test]
     fn part2() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/3")?);
+        let input = open_input(3)?;
         let grid = read_input(input)?;
         assert_eq!(super::part2(&grid), 5813773056);
         Ok(())
     }
+
+    #[test]
+    fn slopes() -> Res<()> {
+        let grid = read_input(open_input(3)?)?;
+
+        let reports = scan_slopes(&grid, 7, 2);
+        // 0/1, then 1/1 through 7/1, then the odd ones over 2.
+        assert_eq!(reports.len(), 1 + 7 + 4);
+        let r = reports
+            .iter()
+            .find(|r| r.slope == Slope { right: 3, down: 1 });
+        assert_eq!(r.unwrap().trees, 234);
+
+        let best = best_slope(&grid, 7, 2).unwrap();
+        assert!(reports.iter().all(|r| r.trees >= best.trees));
+        Ok(())
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let e = read_input("..#\n\n#..\n".as_bytes()).err().unwrap();
+        assert_eq!(e.to_string(), "line 2: malformed line: empty row");
+
+        let e = read_input("..#\n#..\n#.\n".as_bytes()).err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "line 3: malformed line: expected 3 tiles, like row 1, got 2"
+        );
+    }
+
+    #[test]
+    fn steep_slopes() -> Res<()> {
+        assert!(Slope::new(1, 0).is_err());
+
+        // Going 4 down from the top skips the bottom row, and only visits the start, which
+        // hits no trees. That shouldn't count as the best slope.
+        let grid = read_input("..\n##\n##\n".as_bytes())?;
+        let best = best_slope(&grid, 2, 4).unwrap();
+        assert_eq!(best.slope, Slope::new(1, 2)?);
+        assert_eq!((best.trees, best.squares), (1, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn render_path() -> Res<()> {
+        let grid = read_input("..#.\n#...\n..#.\n.#.#\n".as_bytes())?;
+        let slope = Slope { right: 3, down: 1 };
+        assert_eq!(render(&grid, slope), "O.#.\n#..O\n..X.\n.X.#\n");
+        assert_eq!(num_collisions(&grid, slope), 2);
+        Ok(())
+    }
 }
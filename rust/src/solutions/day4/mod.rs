@@ -0,0 +1,130 @@
+use crate::{Error, Res, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use schema::Schema;
+use std::collections::HashMap;
+use std::io::prelude::*;
+
+pub mod schema;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+
+    type Input = Vec<Passport>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<Passport>> {
+        read_input(input)
+    }
+
+    fn part1(passports: &Vec<Passport>) -> Res<usize> {
+        Ok(num_valid(passports, &Schema::default(), false))
+    }
+
+    fn part2(passports: &Vec<Passport>) -> Res<usize> {
+        Ok(num_valid(passports, &Schema::default(), true))
+    }
+}
+
+/// With `strict`, check the values too, not just that the required fields are there.
+pub fn num_valid(passports: &[Passport], schema: &Schema, strict: bool) -> usize {
+    passports
+        .iter()
+        .filter(|&p| schema.is_valid(p, strict))
+        .count()
+}
+
+pub struct Passport {
+    fields: HashMap<String, String>,
+
+    /// The passport's first line in the input, for reports.
+    line: usize,
+}
+
+/// Passports are separated by blank lines.
+fn read_input<R: BufRead>(input: R) -> Res<Vec<Passport>> {
+    lazy_static! {
+        static ref WORD_RE: Regex = Regex::new(r"^(.+):(.+)$").unwrap();
+    }
+
+    let mut passports = vec![];
+    let mut fields = HashMap::new();
+    let mut first_line = 1;
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            if !fields.is_empty() {
+                passports.push(Passport {
+                    fields: std::mem::take(&mut fields),
+                    line: first_line,
+                });
+            }
+            continue;
+        }
+        if fields.is_empty() {
+            first_line = i + 1;
+        }
+
+        for (word_col, word) in schema::words(&line) {
+            let caps = WORD_RE.captures(word).ok_or_else(|| {
+                Error::malformed(format!("expected `key:value`, got `{}`", word))
+                    .at(i + 1, word_col)
+            })?;
+
+            let key = &caps[1];
+            let val = &caps[2];
+            if let Some(old_val) = fields.insert(String::from(key), String::from(val)) {
+                let msg = format!("Duplicate entry {}: {} / {}", key, old_val, val);
+                return Err(Error::invalid(msg).at(i + 1, word_col));
+            }
+        }
+    }
+    if !fields.is_empty() {
+        passports.push(Passport {
+            fields,
+            line: first_line,
+        });
+    }
+    Ok(passports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_input;
+
+    #[test]
+    fn whitespace() -> Res<()> {
+        let input = "ecl:gry\tpid:860033327 eyr:2020\thcl:#fffffd\n\
+                     byr:1937  iyr:2017\tcid:147 hgt:183cm\n";
+        let passports = read_input(input.as_bytes())?;
+        assert_eq!(passports.len(), 1);
+        assert_eq!(num_valid(&passports, &Schema::default(), true), 1);
+
+        let e = read_input("ecl:gry\t\tpid".as_bytes()).err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 10: malformed line: expected `key:value`, got `pid`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part1() -> Res<()> {
+        let input = open_input(4)?;
+        let passports = read_input(input)?;
+        assert_eq!(num_valid(&passports, &Schema::default(), false), 245);
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> Res<()> {
+        let input = open_input(4)?;
+        let passports = read_input(input)?;
+        assert_eq!(num_valid(&passports, &Schema::default(), true), 133);
+        Ok(())
+    }
+}
@@ -0,0 +1,396 @@
+use super::Passport;
+use crate::{Context, Error, Res};
+use regex::Regex;
+use std::fmt;
+use std::io::BufRead;
+use std::path::Path;
+
+/// Which fields a passport needs, and what their values should look like.
+///
+/// The schema file has one line per field: its name, `required` or `optional`, and then any
+/// number of checks, which must all pass. Fields that aren't listed aren't allowed. Blank lines
+/// and `#` comments are ignored.
+///
+/// The checks are:
+/// - `digits N`: exactly N digits.
+/// - `range LOW-HIGH`: a number from LOW through HIGH.
+/// - `units UNIT:LOW-HIGH ...`: a number followed by one of the units, in that unit's range.
+/// - `regex RE`: the whole value matches RE, which can't contain whitespace.
+/// - `oneof A B ...`: one of the listed words. This must be the last check on the line.
+pub struct Schema {
+    fields: Vec<FieldSpec>,
+}
+
+/// The puzzle's rules.
+pub const DEFAULT: &str = "\
+# field  presence  checks
+byr  required  digits 4  range 1920-2002
+iyr  required  digits 4  range 2010-2020
+eyr  required  digits 4  range 2020-2030
+hgt  required  units cm:150-193 in:59-76
+hcl  required  regex #[0-9a-f]{6}
+ecl  required  oneof amb blu brn gry grn hzl oth
+pid  required  regex [0-9]{9}
+cid  optional
+";
+
+struct FieldSpec {
+    name: String,
+    required: bool,
+    checks: Vec<Check>,
+}
+
+enum Check {
+    Digits(usize),
+    Range(u64, u64),
+    Units(Vec<(String, u64, u64)>),
+    Regex(Regex),
+    OneOf(Vec<String>),
+}
+
+/// Why a passport failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    Missing(String),
+
+    /// A field that isn't in the schema.
+    Unknown(String),
+
+    Invalid {
+        field: String,
+        value: String,
+        reason: String,
+    },
+}
+
+/// The problems with one passport. It's valid if there aren't any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// The passport's first line in the input.
+    pub line: usize,
+    pub problems: Vec<Problem>,
+}
+
+impl Schema {
+    pub fn read(input: impl BufRead) -> Res<Schema> {
+        let mut fields: Vec<FieldSpec> = vec![];
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line?;
+            let words = words(&line);
+            if words.is_empty() || words[0].1.starts_with('#') {
+                continue;
+            }
+
+            let spec = FieldSpec::parse(&words).at_line(i + 1)?;
+            if fields.iter().any(|f| f.name == spec.name) {
+                let msg = format!("field {} listed twice", spec.name);
+                return Err(Error::invalid(msg).at(i + 1, 1));
+            }
+            fields.push(spec);
+        }
+
+        Ok(Schema { fields })
+    }
+
+    pub fn load(path: &Path) -> Res<Schema> {
+        Schema::read(crate::open_file(path)?)
+    }
+
+    /// Check a passport against the schema. If `strict` is false, only check that the required
+    /// fields are there.
+    ///
+    /// Problems are in schema order, followed by any unknown fields, in alphabetical order.
+    pub fn check(&self, passport: &Passport, strict: bool) -> Vec<Problem> {
+        let mut problems = vec![];
+
+        for spec in &self.fields {
+            match passport.fields.get(&spec.name) {
+                None if spec.required => problems.push(Problem::Missing(spec.name.clone())),
+                None => (),
+                Some(val) if strict => {
+                    if let Some(reason) = spec.checks.iter().find_map(|c| c.check(val).err()) {
+                        problems.push(Problem::Invalid {
+                            field: spec.name.clone(),
+                            value: val.clone(),
+                            reason,
+                        });
+                    }
+                }
+                Some(_) => (),
+            }
+        }
+
+        if strict {
+            let mut unknown: Vec<_> = passport
+                .fields
+                .keys()
+                .filter(|k| !self.fields.iter().any(|f| &f.name == *k))
+                .collect();
+            unknown.sort_unstable();
+            problems.extend(unknown.into_iter().map(|k| Problem::Unknown(k.clone())));
+        }
+
+        problems
+    }
+
+    pub fn is_valid(&self, passport: &Passport, strict: bool) -> bool {
+        self.check(passport, strict).is_empty()
+    }
+
+    /// A report for each passport, for auditing a whole batch.
+    pub fn audit(&self, passports: &[Passport], strict: bool) -> Vec<Report> {
+        passports
+            .iter()
+            .map(|p| Report {
+                line: p.line,
+                problems: self.check(p, strict),
+            })
+            .collect()
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Schema {
+        Schema::read(DEFAULT.as_bytes()).unwrap()
+    }
+}
+
+/// Each whitespace-separated word, with its column.
+pub(super) fn words(line: &str) -> Vec<(usize, &str)> {
+    let mut words = vec![];
+    let mut start = None;
+    for (col, (i, c)) in line.char_indices().enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((col + 1, i)),
+            (Some((word_col, j)), true) => {
+                words.push((word_col, &line[j..i]));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some((word_col, j)) = start {
+        words.push((word_col, &line[j..]));
+    }
+    words
+}
+
+impl FieldSpec {
+    /// Helper for `Schema::read`.
+    fn parse(words: &[(usize, &str)]) -> Res<FieldSpec> {
+        let name = String::from(words[0].1);
+        let required = match words.get(1) {
+            Some((_, "required")) => true,
+            Some((_, "optional")) => false,
+            Some((col, w)) => {
+                let msg = format!("expected `required` or `optional`, got `{}`", w);
+                return Err(Error::malformed(msg).at_col(*col));
+            }
+            None => return Err(Error::eof("`required` or `optional`")),
+        };
+
+        let mut checks = vec![];
+        let mut rest = words[2..].iter();
+        while let Some(&(col, kind)) = rest.next() {
+            let mut arg = || match rest.next() {
+                Some(&(_, arg)) => Ok(arg),
+                None => Err(Error::eof(format!("an argument for `{}`", kind))),
+            };
+
+            let check = match kind {
+                "digits" => Check::Digits(arg()?.parse().at_col(col)?),
+                "range" => {
+                    let (low, high) = parse_range(arg()?).at_col(col)?;
+                    Check::Range(low, high)
+                }
+                "regex" => {
+                    // Anchor it, so that it has to match the whole value.
+                    let re = Regex::new(&format!("^(?:{})$", arg()?))
+                        .map_err(|e| Error::invalid(format!("bad regex: {}", e)))
+                        .at_col(col)?;
+                    Check::Regex(re)
+                }
+                "units" => {
+                    let mut units = vec![];
+                    while let Some(&(unit_col, w)) = rest.as_slice().first() {
+                        let (unit, range) = match w.split_once(':') {
+                            Some(pair) => pair,
+                            None => break,
+                        };
+                        let (low, high) = parse_range(range).at_col(unit_col)?;
+                        units.push((String::from(unit), low, high));
+                        rest.next();
+                    }
+                    if units.is_empty() {
+                        let msg = "expected at least one unit, e.g. `cm:150-193`, after `units`";
+                        return Err(Error::malformed(msg).at_col(col));
+                    }
+                    Check::Units(units)
+                }
+                "oneof" => {
+                    let options: Vec<_> = rest.by_ref().map(|&(_, w)| String::from(w)).collect();
+                    if options.is_empty() {
+                        return Err(Error::eof("words after `oneof`").at_col(col));
+                    }
+                    Check::OneOf(options)
+                }
+                _ => {
+                    let msg = format!("unknown check `{}`", kind);
+                    return Err(Error::malformed(msg).at_col(col));
+                }
+            };
+            checks.push(check);
+        }
+
+        Ok(FieldSpec {
+            name,
+            required,
+            checks,
+        })
+    }
+}
+
+/// E.g. `150-193`.
+fn parse_range(s: &str) -> Res<(u64, u64)> {
+    let (low, high) = s
+        .split_once('-')
+        .ok_or_else(|| Error::malformed(format!("expected e.g. `1-10`, got `{}`", s)))?;
+    let low = low.parse()?;
+    let high = high.parse()?;
+    if low > high {
+        return Err(Error::invalid(format!("empty range: {}", s)));
+    }
+    Ok((low, high))
+}
+
+impl Check {
+    /// Err has the reason the value fails.
+    fn check(&self, val: &str) -> Result<(), String> {
+        let in_range = |n: &str, low: u64, high: u64, unit: &str| match n.parse::<u64>() {
+            Ok(n) if (low..=high).contains(&n) => Ok(()),
+            Ok(_) => Err(format!("not in {}-{}{}", low, high, unit)),
+            Err(_) => Err(String::from("not a number")),
+        };
+
+        match self {
+            Check::Digits(n) => {
+                if val.len() == *n && val.chars().all(|c| c.is_ascii_digit()) {
+                    Ok(())
+                } else {
+                    Err(format!("not {} digits", n))
+                }
+            }
+            Check::Range(low, high) => in_range(val, *low, *high, ""),
+            Check::Units(units) => {
+                for (unit, low, high) in units {
+                    if let Some(n) = val.strip_suffix(unit.as_str()) {
+                        if !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()) {
+                            return in_range(n, *low, *high, unit);
+                        }
+                    }
+                }
+                let names: Vec<_> = units.iter().map(|(u, _, _)| u.as_str()).collect();
+                Err(format!("not a number of {}", names.join(" or ")))
+            }
+            Check::Regex(re) => {
+                if re.is_match(val) {
+                    Ok(())
+                } else {
+                    // Show the pattern without the anchors `parse` added.
+                    let pattern = re.as_str();
+                    Err(format!("doesn't match {}", &pattern[4..pattern.len() - 2]))
+                }
+            }
+            Check::OneOf(options) => {
+                if options.iter().any(|o| o == val) {
+                    Ok(())
+                } else {
+                    Err(format!("not one of {}", options.join(" ")))
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Missing(field) => write!(f, "{}: missing", field),
+            Problem::Unknown(field) => write!(f, "{}: unknown field", field),
+            Problem::Invalid {
+                field,
+                value,
+                reason,
+            } => write!(f, "{}={}: {}", field, value, reason),
+        }
+    }
+}
+
+/// E.g. `line 5: hgt=190: not a number of cm or in; xyz: unknown field`, or `line 5: valid`.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.problems.is_empty() {
+            return write!(f, "line {}: valid", self.line);
+        }
+        let problems: Vec<_> = self.problems.iter().map(Problem::to_string).collect();
+        write!(f, "line {}: {}", self.line, problems.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::day4::read_input;
+
+    #[test]
+    fn audit() -> Res<()> {
+        let input = "\
+eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719 xyz:1
+";
+        let passports = read_input(input.as_bytes())?;
+        let reports = Schema::default().audit(&passports, true);
+        let reports: Vec<_> = reports.iter().map(Report::to_string).collect();
+        assert_eq!(
+            reports,
+            [
+                "line 1: eyr=1972: not in 2020-2030; hgt=170: not a number of cm or in; \
+                 pid=186cm: doesn't match [0-9]{9}",
+                "line 4: valid",
+                "line 7: xyz: unknown field",
+            ]
+        );
+
+        let lax = Schema::default().audit(&passports, false);
+        assert!(lax.iter().all(|r| r.problems.is_empty()));
+        Ok(())
+    }
+
+    #[test]
+    fn read_errors() {
+        let err = |s: &str| Schema::read(s.as_bytes()).err().unwrap().to_string();
+        assert_eq!(
+            err("byr required\nhgt required units 150-193"),
+            "line 2, column 14: malformed line: expected at least one unit, e.g. `cm:150-193`, \
+             after `units`"
+        );
+        assert_eq!(
+            err("byr  required  range 9-1"),
+            "line 1, column 16: empty range: 9-1"
+        );
+        assert_eq!(
+            err("byr mandatory"),
+            "line 1, column 5: malformed line: expected `required` or `optional`, got `mandatory`"
+        );
+        assert_eq!(
+            err("byr optional\nbyr optional"),
+            "line 2, column 1: field byr listed twice"
+        );
+    }
+}
@@ -1,35 +1,150 @@
-use crate::Res;
-use std::io::{self, prelude::*};
+use crate::{Context, Error, Res, Solution};
+use std::fmt;
+use std::io::prelude::*;
 
-pub fn main() -> Res<()> {
-    let nums = read_input(io::stdin().lock())?;
+pub struct Day5;
 
-    let max = nums.iter().max().expect("Empty input");
-    println!("{}", max);
+impl Solution for Day5 {
+    const DAY: u32 = 5;
 
-    let missing = find_missing(nums).expect("Empty input");
-    println!("{}", missing);
+    type Input = SeatMap;
+    type Part1 = u32;
+    type Part2 = u32;
 
-    Ok(())
+    fn parse(input: &mut dyn BufRead) -> Res<SeatMap> {
+        SeatMap::read(Codec::default(), input)
+    }
+
+    fn part1(map: &SeatMap) -> Res<u32> {
+        let max = map.taken().last().copied().ok_or("Empty input")?;
+        Ok(max.id)
+    }
+
+    fn part2(map: &SeatMap) -> Res<u32> {
+        match map.gaps()[..] {
+            [seat] => Ok(seat.id),
+            [] => Err("No missing seat".into()),
+            ref gaps => Err(format!("{} seats could be yours", gaps.len()).into()),
+        }
+    }
+}
+
+/// How a plane's seats are laid out, and how its boarding passes are written.
+///
+/// A boarding pass is a binary space partition: each character of the row code halves the
+/// remaining rows, keeping the lower half or the upper half. The column code does the same for
+/// columns. So really it's the row and column in binary, with custom digits, most significant
+/// first.
+///
+/// The row and column counts don't have to be powers of two. Then the codes are long enough
+/// for the biggest row or column, and bigger codes are rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Codec {
+    rows: u32,
+    cols: u32,
+
+    /// The lower-half and upper-half characters.
+    row_chars: [char; 2],
+    col_chars: [char; 2],
+
+    row_bits: u32,
+    col_bits: u32,
+}
+
+/// A seat on the plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Seat {
+    /// `row * cols + col`, which orders the seats front to back, left to right.
+    pub id: u32,
+    pub row: u32,
+    pub col: u32,
 }
 
-fn read_input(input: impl BufRead) -> Res<Vec<u32>> {
-    let mut nums = vec![];
-    for line in input.lines() {
-        nums.push(parse_num(&line?)?);
+impl Codec {
+    pub fn new(rows: u32, cols: u32, row_chars: [char; 2], col_chars: [char; 2]) -> Res<Codec> {
+        if rows == 0 || cols == 0 {
+            return Err("A plane needs at least one row and one column".into());
+        }
+        if rows.checked_mul(cols).is_none() {
+            return Err(format!("Too many seats: {} x {}", rows, cols).into());
+        }
+        if row_chars[0] == row_chars[1] || col_chars[0] == col_chars[1] {
+            return Err("The lower and upper half need different characters".into());
+        }
+
+        // The number of bits needed for the numbers 0..n.
+        let bits = |n: u32| u32::BITS - (n - 1).leading_zeros();
+        Ok(Codec {
+            rows,
+            cols,
+            row_chars,
+            col_chars,
+            row_bits: bits(rows),
+            col_bits: bits(cols),
+        })
+    }
+
+    pub fn num_seats(&self) -> u32 {
+        self.rows * self.cols
+    }
+
+    pub fn seat(&self, row: u32, col: u32) -> Option<Seat> {
+        if row < self.rows && col < self.cols {
+            Some(Seat {
+                id: row * self.cols + col,
+                row,
+                col,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn seat_by_id(&self, id: u32) -> Option<Seat> {
+        self.seat(id / self.cols, id % self.cols)
+    }
+
+    /// E.g. `FBFBBFFRLR` is row 44, column 5, on the standard plane. Errors have the column of
+    /// the pass where they went wrong.
+    pub fn decode(&self, pass: &str) -> Res<Seat> {
+        let chars: Vec<char> = pass.chars().collect();
+        let len = (self.row_bits + self.col_bits) as usize;
+        if chars.len() != len {
+            let msg = format!("expected {} characters, got {}", len, chars.len());
+            return Err(Error::malformed(msg));
+        }
+
+        let (row_code, col_code) = chars.split_at(self.row_bits as usize);
+        let row = decode_bits(row_code, self.row_chars, 1)?;
+        let col = decode_bits(col_code, self.col_chars, self.row_bits as usize + 1)?;
+
+        self.seat(row, col).ok_or_else(|| {
+            let msg = format!("row {}, column {} isn't on the plane", row, col);
+            Error::invalid(msg)
+        })
+    }
+
+    pub fn encode(&self, seat: Seat) -> String {
+        let mut pass = encode_bits(seat.row, self.row_bits, self.row_chars);
+        pass.push_str(&encode_bits(seat.col, self.col_bits, self.col_chars));
+        pass
     }
-    Ok(nums)
 }
 
-fn parse_num(bsp_code: &str) -> Res<u32> {
+/// The standard plane: 128 rows and 8 columns, with `F`ront/`B`ack and `L`eft/`R`ight.
+impl Default for Codec {
+    fn default() -> Codec {
+        Codec::new(128, 8, ['F', 'B'], ['L', 'R']).unwrap()
+    }
+}
+
+/// Helper for `Codec::decode`. `first_col` is where `code` starts in the pass, for errors.
+fn decode_bits(code: &[char], digits: [char; 2], first_col: usize) -> Res<u32> {
     let mut acc = 0;
-    for c in bsp_code.chars() {
-        let bit = match c {
-            'F' => 0,
-            'B' => 1,
-            'L' => 0,
-            'R' => 1,
-            _ => return Err(format!("Invalid char: {}", c).into()),
+    for (i, &c) in code.iter().enumerate() {
+        let bit = match digits.iter().position(|&d| d == c) {
+            Some(bit) => bit as u32,
+            None => return Err(Error::invalid_char(c).at_col(first_col + i)),
         };
         acc <<= 1;
         acc |= bit;
@@ -37,39 +152,145 @@ fn parse_num(bsp_code: &str) -> Res<u32> {
     Ok(acc)
 }
 
-fn find_missing(mut nums: Vec<u32>) -> Option<u32> {
-    nums.sort();
-    for i in 1..nums.len() {
-        let prev = nums[i - 1];
-        let curr = nums[i];
-        if curr > prev + 1 {
-            return Some(prev + 1);
+/// Helper for `Codec::encode`.
+fn encode_bits(n: u32, num_bits: u32, digits: [char; 2]) -> String {
+    (0..num_bits)
+        .rev()
+        .map(|i| digits[(n >> i) as usize & 1])
+        .collect()
+}
+
+/// Which seats are taken.
+pub struct SeatMap {
+    codec: Codec,
+
+    /// Indexed by seat id.
+    taken: Vec<bool>,
+}
+
+impl SeatMap {
+    /// One boarding pass per line.
+    pub fn read(codec: Codec, input: impl BufRead) -> Res<SeatMap> {
+        let mut taken = vec![false; codec.num_seats() as usize];
+        for (i, line) in input.lines().enumerate() {
+            let seat = codec.decode(&line?).at_line(i + 1)?;
+            if taken[seat.id as usize] {
+                let msg = format!("seat {} is taken twice", seat.id);
+                return Err(Error::invalid(msg).at_line(i + 1));
+            }
+            taken[seat.id as usize] = true;
         }
+        Ok(SeatMap { codec, taken })
+    }
+
+    fn seats(&self, taken: bool) -> Vec<Seat> {
+        (0..self.codec.num_seats())
+            .filter(|&id| self.taken[id as usize] == taken)
+            .map(|id| self.codec.seat_by_id(id).unwrap())
+            .collect()
+    }
+
+    /// In order of id.
+    pub fn taken(&self) -> Vec<Seat> {
+        self.seats(true)
+    }
+
+    /// In order of id.
+    pub fn vacant(&self) -> Vec<Seat> {
+        self.seats(false)
+    }
+
+    /// Vacant seats where the seats on either side, by id, are taken. Only one of these should
+    /// be yours.
+    pub fn gaps(&self) -> Vec<Seat> {
+        let is_taken = |id: u32| self.taken.get(id as usize) == Some(&true);
+        self.vacant()
+            .into_iter()
+            .filter(|s| s.id > 0 && is_taken(s.id - 1) && is_taken(s.id + 1))
+            .collect()
+    }
+}
+
+/// One line per row, with the row's code: `#` for a taken seat, `.` for a vacant one, and `?`
+/// for a gap.
+impl fmt::Display for SeatMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let codec = &self.codec;
+        let gaps = self.gaps();
+        for row in 0..codec.rows {
+            let code = encode_bits(row, codec.row_bits, codec.row_chars);
+            write!(f, "{} ", code)?;
+            for col in 0..codec.cols {
+                let seat = codec.seat(row, col).unwrap();
+                let c = if self.taken[seat.id as usize] {
+                    '#'
+                } else if gaps.contains(&seat) {
+                    '?'
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
     }
-    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
-    use std::io::BufReader;
+    use crate::open_input;
 
     #[test]
     fn part1() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/5")?);
-        let nums = read_input(input)?;
-        let max = nums.into_iter().max().unwrap();
-        assert_eq!(max, 816);
+        let map = SeatMap::read(Codec::default(), open_input(5)?)?;
+        assert_eq!(map.taken().last().unwrap().id, 816);
         Ok(())
     }
 
     #[test]
     fn part2() -> Res<()> {
-        let input = BufReader::new(File::open("../inputs/5")?);
-        let nums = read_input(input)?;
-        let missing = find_missing(nums).unwrap();
-        assert_eq!(missing, 539);
+        let map = SeatMap::read(Codec::default(), open_input(5)?)?;
+        let gaps: Vec<_> = map.gaps().iter().map(|s| s.id).collect();
+        assert_eq!(gaps, [539]);
+        Ok(())
+    }
+
+    #[test]
+    fn codec() -> Res<()> {
+        let standard = Codec::default();
+        let seat = standard.decode("FBFBBFFRLR")?;
+        assert_eq!((seat.row, seat.col, seat.id), (44, 5, 357));
+        assert_eq!(standard.encode(seat), "FBFBBFFRLR");
+
+        // 5 rows need 3 bits, and 6 columns need 3 bits.
+        let small = Codec::new(5, 6, ['0', '1'], ['<', '>'])?;
+        for id in 0..small.num_seats() {
+            let seat = small.seat_by_id(id).unwrap();
+            assert_eq!(small.decode(&small.encode(seat))?, seat);
+        }
+
+        let err = |codec: &Codec, pass| codec.decode(pass).unwrap_err().to_string();
+        assert_eq!(err(&small, "101<<<"), "row 5, column 0 isn't on the plane");
+        assert_eq!(err(&small, "100<x<"), "column 5: invalid character 'x'");
+        assert_eq!(
+            err(&standard, "FBF"),
+            "malformed line: expected 10 characters, got 3"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn seat_map() -> Res<()> {
+        let codec = Codec::new(2, 3, ['F', 'B'], ['L', 'R'])?;
+        let passes = "FLL\nFLR\nBLL\nBLR\n";
+        let map = SeatMap::read(codec, passes.as_bytes())?;
+
+        let ids = |seats: Vec<Seat>| seats.iter().map(|s| s.id).collect::<Vec<_>>();
+        assert_eq!(ids(map.vacant()), [2, 5]);
+        assert_eq!(ids(map.gaps()), [2]);
+        assert_eq!(map.to_string(), "F ##?\nB ##.\n");
         Ok(())
     }
 }
@@ -0,0 +1,123 @@
+/// A set of small non-negative integers, stored one bit each. It grows as needed.
+#[derive(Debug, Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+impl BitSet {
+    pub fn new() -> BitSet {
+        BitSet::default()
+    }
+
+    /// The set `0..n`.
+    pub fn full(n: usize) -> BitSet {
+        let mut set = BitSet::new();
+        for i in 0..n {
+            set.insert(i);
+        }
+        set
+    }
+
+    pub fn insert(&mut self, i: usize) {
+        let (word, bit) = (i / WORD_BITS, i % WORD_BITS);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << bit;
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        let (word, bit) = (i / WORD_BITS, i % WORD_BITS);
+        self.words.get(word).is_some_and(|w| w & (1 << bit) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// In increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &w)| {
+            (0..WORD_BITS)
+                .filter(move |bit| w & (1 << bit) != 0)
+                .map(move |bit| i * WORD_BITS + bit)
+        })
+    }
+
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    /// Combine word by word. Missing words count as 0.
+    fn zip_with(&self, other: &BitSet, f: impl Fn(u64, u64) -> u64) -> BitSet {
+        let n = self.words.len().max(other.words.len());
+        let word = |set: &BitSet, i| set.words.get(i).copied().unwrap_or(0);
+        BitSet {
+            words: (0..n).map(|i| f(word(self, i), word(other, i))).collect(),
+        }
+    }
+}
+
+/// Trailing zero words don't matter.
+impl PartialEq for BitSet {
+    fn eq(&self, other: &BitSet) -> bool {
+        self.symmetric_difference(other).is_empty()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> BitSet {
+        let mut set = BitSet::new();
+        for i in iter {
+            set.insert(i);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_ops() {
+        let a: BitSet = [1, 3, 70, 200].into_iter().collect();
+        let b: BitSet = [3, 4, 70].into_iter().collect();
+
+        let items = |s: BitSet| s.iter().collect::<Vec<_>>();
+        assert_eq!(items(a.union(&b)), [1, 3, 4, 70, 200]);
+        assert_eq!(items(a.intersection(&b)), [3, 70]);
+        assert_eq!(items(a.difference(&b)), [1, 200]);
+        assert_eq!(items(a.symmetric_difference(&b)), [1, 4, 200]);
+        assert_eq!(a.len(), 4);
+        assert!(a.contains(200) && !a.contains(199) && !a.contains(1000));
+
+        // Equal, even though `a` has more words.
+        assert_eq!(
+            a.intersection(&b),
+            b.difference(&BitSet::full(5))
+                .union(&[3].into_iter().collect())
+        );
+        assert!(BitSet::new().is_empty());
+    }
+}
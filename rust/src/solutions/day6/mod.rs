@@ -0,0 +1,212 @@
+use crate::{Error, Res, Solution};
+use bitset::BitSet;
+use std::collections::{BTreeMap, HashMap};
+use std::io::prelude::*;
+
+pub mod bitset;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+
+    type Input = Survey;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Survey> {
+        Survey::read(input)
+    }
+
+    fn part1(survey: &Survey) -> Res<usize> {
+        Ok(part1(survey))
+    }
+
+    fn part2(survey: &Survey) -> Res<usize> {
+        Ok(part2(survey))
+    }
+}
+
+fn part1(survey: &Survey) -> usize {
+    survey.groups.iter().map(|g| g.anyone().len()).sum()
+}
+
+fn part2(survey: &Survey) -> usize {
+    survey.groups.iter().map(|g| g.everyone().len()).sum()
+}
+
+/// Everyone's answers, in groups.
+///
+/// Each question is a character, and gets a number in order of first appearance. Sets of
+/// questions are `BitSet`s of those numbers; use `chars` to turn them back into characters.
+pub struct Survey {
+    pub groups: Vec<Group>,
+
+    /// Indexed by question number.
+    questions: Vec<char>,
+}
+
+pub struct Group {
+    /// The questions each person answered "yes" to.
+    pub people: Vec<BitSet>,
+}
+
+impl Survey {
+    /// One person per line, with a character for each question they answered "yes" to. Groups
+    /// are separated by blank lines.
+    pub fn read(input: impl BufRead) -> Res<Survey> {
+        let mut groups = vec![];
+        let mut people = vec![];
+        let mut questions = vec![];
+        let mut numbers = HashMap::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                if !people.is_empty() {
+                    groups.push(Group {
+                        people: std::mem::take(&mut people),
+                    });
+                }
+                continue;
+            }
+
+            let mut person = BitSet::new();
+            for (j, c) in line.chars().enumerate() {
+                if c.is_whitespace() {
+                    return Err(Error::invalid_char(c).at(i + 1, j + 1));
+                }
+                let n = *numbers.entry(c).or_insert_with(|| {
+                    questions.push(c);
+                    questions.len() - 1
+                });
+                person.insert(n);
+            }
+            people.push(person);
+        }
+        if !people.is_empty() {
+            groups.push(Group { people });
+        }
+
+        Ok(Survey { groups, questions })
+    }
+
+    /// The questions in the set, sorted.
+    pub fn chars(&self, set: &BitSet) -> String {
+        let mut chars: Vec<_> = set.iter().map(|n| self.questions[n]).collect();
+        chars.sort_unstable();
+        chars.into_iter().collect()
+    }
+
+    /// How many people in the group answered "yes" to each question. Questions nobody answered
+    /// are left out.
+    pub fn histogram(&self, group: &Group) -> BTreeMap<char, usize> {
+        group
+            .histogram()
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(n, &count)| (self.questions[n], count))
+            .collect()
+    }
+}
+
+impl Group {
+    /// The questions anyone answered "yes" to.
+    pub fn anyone(&self) -> BitSet {
+        self.people
+            .iter()
+            .fold(BitSet::new(), |acc, p| acc.union(p))
+    }
+
+    /// The questions everyone answered "yes" to.
+    pub fn everyone(&self) -> BitSet {
+        match self.people.split_first() {
+            Some((first, rest)) => rest
+                .iter()
+                .fold(first.clone(), |acc, p| acc.intersection(p)),
+            None => BitSet::new(),
+        }
+    }
+
+    /// The questions at least `k` people answered "yes" to.
+    pub fn at_least(&self, k: usize) -> BitSet {
+        self.matching(|count| count >= k)
+    }
+
+    /// The questions exactly `k` people answered "yes" to.
+    pub fn exactly(&self, k: usize) -> BitSet {
+        self.matching(|count| count == k)
+    }
+
+    /// The symmetric difference of everyone's answers, i.e., the questions an odd number of
+    /// people answered "yes" to.
+    pub fn symmetric_difference(&self) -> BitSet {
+        self.people
+            .iter()
+            .fold(BitSet::new(), |acc, p| acc.symmetric_difference(p))
+    }
+
+    /// How many people answered "yes" to each question, indexed by question number. Trailing
+    /// zeros may be left off.
+    pub fn histogram(&self) -> Vec<usize> {
+        let mut counts = vec![];
+        for p in &self.people {
+            for n in p.iter() {
+                if n >= counts.len() {
+                    counts.resize(n + 1, 0);
+                }
+                counts[n] += 1;
+            }
+        }
+        counts
+    }
+
+    fn matching(&self, pred: impl Fn(usize) -> bool) -> BitSet {
+        let counts = self.histogram();
+        (0..counts.len()).filter(|&n| pred(counts[n])).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_input;
+
+    #[test]
+    fn part1() -> Res<()> {
+        let survey = Survey::read(open_input(6)?)?;
+        assert_eq!(super::part1(&survey), 6775);
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> Res<()> {
+        let survey = Survey::read(open_input(6)?)?;
+        assert_eq!(super::part2(&survey), 3356);
+        Ok(())
+    }
+
+    #[test]
+    fn queries() -> Res<()> {
+        // Not just a-z.
+        let survey = Survey::read("abcÉ\nab7\nbÉ\n".as_bytes())?;
+        let group = &survey.groups[0];
+        assert_eq!(survey.chars(&group.anyone()), "7abcÉ");
+        assert_eq!(survey.chars(&group.everyone()), "b");
+        assert_eq!(survey.chars(&group.at_least(2)), "abÉ");
+        assert_eq!(survey.chars(&group.exactly(1)), "7c");
+        assert_eq!(survey.chars(&group.symmetric_difference()), "7bc");
+
+        let histogram: Vec<_> = survey.histogram(group).into_iter().collect();
+        assert_eq!(
+            histogram,
+            [('7', 1), ('a', 2), ('b', 3), ('c', 1), ('É', 2)]
+        );
+
+        let err = Survey::read("ab\n\na b\n".as_bytes()).err().unwrap();
+        assert_eq!(err.to_string(), "line 3, column 2: invalid character ' '");
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{col_of, Context, Error, Res, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::io::prelude::*;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+
+    type Input = Graph;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Graph> {
+        Graph::read(input)
+    }
+
+    fn part1(graph: &Graph) -> Res<usize> {
+        Ok(graph.count_ancestors(START))
+    }
+
+    fn part2(graph: &Graph) -> Res<u64> {
+        graph.weighted_child_count(START)
+    }
+}
+
+const START: &str = "shiny gold";
+
+/// Which bags must contain which other bags, and how many of them.
+pub struct Graph {
+    /// {color -> {child_color -> num_copies}}
+    edges: HashMap<String, HashMap<String, u64>>,
+
+    /// {color -> {parent_color -> num_copies}}
+    back_edges: HashMap<String, HashMap<String, u64>>,
+}
+
+impl Graph {
+    /// Each line looks like this:
+    /// light red bags contain 1 bright white bag, 2 muted yellow bags.
+    fn read(input: impl BufRead) -> Res<Graph> {
+        let mut graph = Graph {
+            edges: HashMap::new(),
+            back_edges: HashMap::new(),
+        };
+        for (i, line) in input.lines().enumerate() {
+            graph.add_rule(&line?).at_line(i + 1)?;
+        }
+        Ok(graph)
+    }
+
+    /// Helper for `read`.
+    fn add_rule(&mut self, line: &str) -> Res<()> {
+        lazy_static! {
+            static ref RULE_RE: Regex = Regex::new(r"^(.*) bags contain (.*)\.$").unwrap();
+            static ref EDGE_RE: Regex = Regex::new(r"^(\d+) (.*) bags?$").unwrap();
+        }
+
+        let caps = RULE_RE.captures(line).ok_or_else(|| {
+            Error::malformed(format!(
+                "expected e.g. `x bags contain 1 y bag.`, got `{}`",
+                line
+            ))
+        })?;
+        let color = &caps[1];
+        let edges = &caps[2];
+
+        if edges == "no other bags" {
+            return Ok(());
+        }
+
+        let mut col = col_of(line, caps.get(2).unwrap().start());
+        for e in edges.split(", ") {
+            let caps = EDGE_RE.captures(e).ok_or_else(|| {
+                Error::malformed(format!("expected e.g. `2 y bags`, got `{}`", e)).at_col(col)
+            })?;
+            let num_copies: u64 = caps[1].parse().at_col(col)?;
+            if num_copies == 0 {
+                return Err(Error::invalid("A bag can't contain 0 copies of another").at_col(col));
+            }
+            col += e.chars().count() + 2;
+
+            let child_color = &caps[2];
+            let children = self.edges.entry(String::from(color)).or_default();
+            if children
+                .insert(String::from(child_color), num_copies)
+                .is_some()
+            {
+                return Err(format!("{} listed twice in rule for {}", child_color, color).into());
+            }
+            self.back_edges
+                .entry(String::from(child_color))
+                .or_default()
+                .insert(String::from(color), num_copies);
+        }
+        Ok(())
+    }
+
+    /// How many colors of bag eventually contain at least one `start` bag?
+    fn count_ancestors(&self, start: &str) -> usize {
+        let mut seen = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(color) = stack.pop() {
+            if !seen.insert(color) {
+                continue;
+            }
+            if let Some(parents) = self.back_edges.get(color) {
+                stack.extend(parents.keys().map(String::as_str));
+            }
+        }
+        seen.len() - 1 // Exclude start.
+    }
+
+    /// How many bags are inside a single `start` bag?
+    ///
+    /// Returns an error if a bag contains itself, since then the answer is infinite, or if the
+    /// answer doesn't fit in a u64.
+    fn weighted_child_count(&self, start: &str) -> Res<u64> {
+        // This is the set of nodes on the path from the start node to the current node. We use it
+        // to detect cycles.
+        let mut current_path = HashSet::new();
+        let mut counts = HashMap::new();
+        let total = self.count_helper(start, &mut current_path, &mut counts)?;
+        Ok(total - 1) // Exclude start.
+    }
+
+    /// Helper for `weighted_child_count`. Counts `color` itself, too.
+    ///
+    /// `counts` remembers the answer for each color, since a color can be reached along many
+    /// paths, and recounting it each time would take exponential time.
+    fn count_helper<'a>(
+        &'a self,
+        color: &'a str,
+        current_path: &mut HashSet<&'a str>,
+        counts: &mut HashMap<&'a str, u64>,
+    ) -> Res<u64> {
+        if let Some(&total) = counts.get(color) {
+            return Ok(total);
+        }
+        current_path.insert(color);
+
+        let mut total: u64 = 1; // Self.
+        for (child, &weight) in self.edges.get(color).into_iter().flatten() {
+            if current_path.contains(child.as_str()) {
+                return Err(format!("Cycle: {} bags contain themselves", child).into());
+            }
+            let inside = self.count_helper(child, current_path, counts)?;
+            total = weight
+                .checked_mul(inside)
+                .and_then(|n| total.checked_add(n))
+                .ok_or_else(|| Error::invalid(format!("Too many bags inside {} bags", color)))?;
+        }
+
+        current_path.remove(color);
+        counts.insert(color, total);
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_input;
+
+    #[test]
+    fn part1() -> Res<()> {
+        let graph = Graph::read(open_input(7)?)?;
+        assert_eq!(graph.count_ancestors(START), 213);
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> Res<()> {
+        let graph = Graph::read(open_input(7)?)?;
+        assert_eq!(graph.weighted_child_count(START)?, 38426);
+        Ok(())
+    }
+
+    #[test]
+    fn cycle() -> Res<()> {
+        let input = "shiny gold bags contain 2 dark red bags.\n\
+                     dark red bags contain 1 shiny gold bag.";
+        let graph = Graph::read(input.as_bytes())?;
+        assert_eq!(graph.count_ancestors(START), 1);
+        assert!(graph.weighted_child_count(START).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn deep_and_wide() -> Res<()> {
+        // Each bag holds one of each bag in the next layer, so there are 2^61 paths to the
+        // bottom, and this would never finish without memoization.
+        let mut input = String::from("shiny gold bags contain 1 l0 a bag, 1 l0 b bag.\n");
+        for i in 0..60 {
+            for side in ["a", "b"] {
+                input += &format!(
+                    "l{} {} bags contain 1 l{} a bag, 1 l{} b bag.\n",
+                    i,
+                    side,
+                    i + 1,
+                    i + 1
+                );
+            }
+        }
+        let graph = Graph::read(input.as_bytes())?;
+        assert_eq!(graph.weighted_child_count(START)?, (1 << 62) - 2);
+
+        // 1000 times as many doesn't fit in a u64.
+        let input = input.replace("contain 1 l0 a bag, 1 l0 b bag", "contain 1000 l0 a bags");
+        let graph = Graph::read(input.as_bytes())?;
+        let e = graph.weighted_child_count(START).unwrap_err();
+        assert_eq!(e.to_string(), "Too many bags inside shiny gold bags");
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_column() {
+        let input = "café bags contain 1 red bag, x blue bags.";
+        let e = Graph::read(input.as_bytes()).err().unwrap();
+        assert_eq!(e.location().col, Some(30));
+    }
+}
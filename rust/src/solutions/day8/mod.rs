@@ -0,0 +1,56 @@
+use crate::{Res, Solution};
+use std::io::BufRead;
+use vm::{Instr, Outcome};
+
+pub mod vm;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+
+    type Input = Vec<Instr>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Vec<Instr>> {
+        vm::read_program(input)
+    }
+
+    /// The accumulator, just before any instruction runs a second time.
+    fn part1(code: &Vec<Instr>) -> Res<i64> {
+        match vm::run(code)? {
+            Outcome::Loop { acc, .. } => Ok(acc),
+            outcome => {
+                Err(format!("Expected an infinite loop, but the program {}", outcome).into())
+            }
+        }
+    }
+
+    /// The accumulator, after repairing the program so that it terminates.
+    fn part2(code: &Vec<Instr>) -> Res<i64> {
+        let (_, acc) =
+            vm::repair(code).ok_or("No single jmp/nop swap makes the program terminate")?;
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_input;
+
+    #[test]
+    fn part1() -> Res<()> {
+        let code = vm::read_program(open_input(8)?)?;
+        assert_eq!(Day8::part1(&code)?, 1671);
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> Res<()> {
+        let code = vm::read_program(open_input(8)?)?;
+        assert_eq!(Day8::part2(&code)?, 892);
+        Ok(())
+    }
+}
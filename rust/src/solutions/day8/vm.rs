@@ -0,0 +1,221 @@
+//! The handheld game console's interpreter.
+
+use crate::{Context, Error, Res};
+use std::collections::HashSet;
+use std::fmt;
+use std::io::BufRead;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// Add the argument to the accumulator.
+    Acc,
+
+    /// Jump relative to the current instruction.
+    Jmp,
+
+    /// Do nothing.
+    Nop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instr {
+    pub op: Op,
+    pub arg: i64,
+}
+
+/// How a program stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The program counter moved to just past the last instruction.
+    Terminated { acc: i64 },
+
+    /// The instruction at `pc` was about to run for a second time.
+    Loop { pc: usize, acc: i64 },
+
+    /// The program jumped somewhere other than an instruction or the end of the program.
+    OutOfBounds { pc: i64, acc: i64 },
+}
+
+/// One instruction being executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub pc: usize,
+    pub instr: Instr,
+
+    /// The accumulator after the instruction ran.
+    pub acc: i64,
+}
+
+/// Each line looks like `acc +8` or `jmp -3`.
+pub fn read_program(input: impl BufRead) -> Res<Vec<Instr>> {
+    let mut code = vec![];
+    for (i, line) in input.lines().enumerate() {
+        code.push(Instr::new(&line?).at_line(i + 1)?);
+    }
+    Ok(code)
+}
+
+impl Instr {
+    fn new(line: &str) -> Res<Instr> {
+        let (op, arg) = match line.split_once(' ') {
+            Some(words) => words,
+            None => {
+                return Err(Error::malformed(format!(
+                    "expected e.g. `acc +8`, got `{}`",
+                    line
+                )))
+            }
+        };
+        let op = match op {
+            "acc" => Op::Acc,
+            "jmp" => Op::Jmp,
+            "nop" => Op::Nop,
+            _ => return Err(Error::malformed(format!("Not an operation: {}", op)).at_col(1)),
+        };
+        let arg = arg.parse().at_col(5)?;
+        Ok(Instr { op, arg })
+    }
+}
+
+/// Run the program until it stops, one way or another.
+///
+/// It's an error if the accumulator or the program counter overflows, with the line of the
+/// instruction that did it.
+pub fn run(code: &[Instr]) -> Res<Outcome> {
+    execute(code, |_| ())
+}
+
+/// Like `run`, but also returns every step that was executed, in order.
+pub fn trace(code: &[Instr]) -> Res<(Outcome, Vec<Step>)> {
+    let mut steps = vec![];
+    let outcome = execute(code, |step| steps.push(step))?;
+    Ok((outcome, steps))
+}
+
+/// Helper for `run` and `trace`. Calls `on_step` after each instruction.
+fn execute(code: &[Instr], mut on_step: impl FnMut(Step)) -> Res<Outcome> {
+    let mut acc: i64 = 0;
+    let mut seen = HashSet::new();
+
+    let mut pc: i64 = 0;
+    while let Some(&instr) = usize::try_from(pc).ok().and_then(|pc| code.get(pc)) {
+        let curr = pc as usize;
+        if !seen.insert(curr) {
+            return Ok(Outcome::Loop { pc: curr, acc });
+        }
+
+        let overflow = |what| Error::invalid(format!("{} overflowed", what)).at_line(curr + 1);
+        match instr.op {
+            Op::Acc => {
+                acc = acc
+                    .checked_add(instr.arg)
+                    .ok_or_else(|| overflow("accumulator"))?;
+                pc += 1;
+            }
+            Op::Jmp => {
+                pc = pc
+                    .checked_add(instr.arg)
+                    .ok_or_else(|| overflow("program counter"))?
+            }
+            Op::Nop => pc += 1,
+        }
+        on_step(Step {
+            pc: curr,
+            instr,
+            acc,
+        });
+    }
+
+    if pc == code.len() as i64 {
+        Ok(Outcome::Terminated { acc })
+    } else {
+        Ok(Outcome::OutOfBounds { pc, acc })
+    }
+}
+
+/// Find the single `jmp` or `nop` that, when swapped for the other, makes the program terminate.
+///
+/// Returns the index of that instruction, along with the final accumulator. Swaps that make the
+/// program overflow don't count as terminating.
+pub fn repair(code: &[Instr]) -> Option<(usize, i64)> {
+    let mut code = code.to_vec();
+    for i in 0..code.len() {
+        let original = code[i];
+        let flipped = match original.op {
+            Op::Acc => continue,
+            Op::Jmp => Op::Nop,
+            Op::Nop => Op::Jmp,
+        };
+
+        code[i].op = flipped;
+        let outcome = run(&code);
+        code[i] = original;
+
+        if let Ok(Outcome::Terminated { acc }) = outcome {
+            return Some((i, acc));
+        }
+    }
+    None
+}
+
+/// E.g. `terminated with acc = 5`.
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Terminated { acc } => write!(f, "terminated with acc = {}", acc),
+            Outcome::Loop { pc, acc } => {
+                write!(f, "looped at instruction {} with acc = {}", pc, acc)
+            }
+            Outcome::OutOfBounds { pc, acc } => {
+                write!(f, "jumped out of bounds to {} with acc = {}", pc, acc)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+    #[test]
+    fn example() -> Res<()> {
+        let code = read_program(EXAMPLE.as_bytes())?;
+        assert_eq!(run(&code)?, Outcome::Loop { pc: 1, acc: 5 });
+        assert_eq!(repair(&code), Some((7, 8)));
+        Ok(())
+    }
+
+    #[test]
+    fn trace_and_bounds() -> Res<()> {
+        let code = read_program("acc +2\njmp +2\nacc +1\nnop -5".as_bytes())?;
+        let (outcome, steps) = trace(&code)?;
+        assert_eq!(outcome, Outcome::Terminated { acc: 2 });
+        let pcs: Vec<_> = steps.iter().map(|s| s.pc).collect();
+        assert_eq!(pcs, vec![0, 1, 3]);
+
+        let code = read_program("acc +2\njmp -2".as_bytes())?;
+        assert_eq!(run(&code)?, Outcome::OutOfBounds { pc: -1, acc: 2 });
+
+        assert!(read_program("mul +2".as_bytes()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() -> Res<()> {
+        let big = i64::MAX;
+        let code = read_program(format!("acc +{}\nacc +1", big).as_bytes())?;
+        let e = run(&code).unwrap_err();
+        assert_eq!(e.to_string(), "line 2: accumulator overflowed");
+
+        let code = read_program(format!("nop +0\njmp +{}", big).as_bytes())?;
+        let e = run(&code).unwrap_err();
+        assert_eq!(e.to_string(), "line 2: program counter overflowed");
+
+        // Flipping the `nop` would overflow, so the fix is the `jmp`.
+        let code = read_program(format!("acc +1\nnop +{}\njmp +0", big).as_bytes())?;
+        assert_eq!(repair(&code), Some((2, 1)));
+        Ok(())
+    }
+}
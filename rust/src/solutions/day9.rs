@@ -0,0 +1,146 @@
+use super::day1::first_k_sum;
+use crate::{Context, Error, Res, Solution};
+use std::io::prelude::*;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+
+    type Input = Xmas;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &mut dyn BufRead) -> Res<Xmas> {
+        read_input(input)
+    }
+
+    fn part1(xmas: &Xmas) -> Res<i64> {
+        let anomaly = find_anomaly(&xmas.nums, xmas.preamble).ok_or("No anomaly found")?;
+        Ok(anomaly)
+    }
+
+    fn part2(xmas: &Xmas) -> Res<i64> {
+        let anomaly = find_anomaly(&xmas.nums, xmas.preamble).ok_or("No anomaly found")?;
+        let range = contiguous_sum(&xmas.nums, anomaly)?.ok_or("No range found")?;
+
+        let min = range.iter().min().unwrap();
+        let max = range.iter().max().unwrap();
+        Ok(min + max)
+    }
+}
+
+/// The numbers sent, and how many are sent before any are checked.
+pub struct Xmas {
+    pub nums: Vec<i64>,
+    pub preamble: usize,
+}
+
+/// The puzzle's preamble length. Its example uses 5.
+pub const PREAMBLE: usize = 25;
+
+/// One number per line, optionally after a `preamble: <n>` line; otherwise it's `PREAMBLE`.
+fn read_input(input: impl BufRead) -> Res<Xmas> {
+    let mut preamble = PREAMBLE;
+    let mut nums = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        match line.strip_prefix("preamble: ") {
+            Some(n) if i == 0 => preamble = n.parse().at(1, "preamble: ".len() + 1)?,
+            _ => nums.push(line.parse().at_line(i + 1)?),
+        }
+    }
+    Ok(Xmas { nums, preamble })
+}
+
+/// Find the first number that isn't the sum of two of the `preamble` numbers before it.
+pub fn find_anomaly(nums: &[i64], preamble: usize) -> Option<i64> {
+    (preamble..nums.len())
+        .find(|&i| first_k_sum(&nums[i - preamble..i], 2, nums[i]).is_none())
+        .map(|i| nums[i])
+}
+
+/// Find a slice of at least two numbers that add up to `target`.
+///
+/// This uses a sliding window, so the numbers can't be negative; that's an error. A negative
+/// target has no such slice.
+pub fn contiguous_sum(nums: &[i64], target: i64) -> Res<Option<&[i64]>> {
+    if let Some(n) = nums.iter().find(|&&n| n < 0) {
+        let msg = format!("Can't find a contiguous sum with a negative number: {}", n);
+        return Err(Error::invalid(msg));
+    }
+    if target < 0 {
+        return Ok(None);
+    }
+
+    // The current window is nums[lo..hi].
+    let mut lo = 0;
+    let mut hi = 0;
+    let mut window_sum = 0;
+    loop {
+        if window_sum == target && hi - lo >= 2 {
+            return Ok(Some(&nums[lo..hi]));
+        }
+
+        if window_sum <= target {
+            if hi == nums.len() {
+                // No slice sums to target.
+                return Ok(None);
+            }
+            window_sum += nums[hi];
+            hi += 1;
+        } else {
+            window_sum -= nums[lo];
+            lo += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_input;
+
+    #[test]
+    fn example() -> Res<()> {
+        let input = "preamble: 5\n35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n\
+                     127\n219\n299\n277\n309\n576\n";
+        let xmas = read_input(input.as_bytes())?;
+        assert_eq!(xmas.preamble, 5);
+        assert_eq!(Day9::part1(&xmas)?, 127);
+        assert_eq!(Day9::part2(&xmas)?, 62);
+
+        let nums = &xmas.nums;
+        assert_eq!(find_anomaly(nums, 5), Some(127));
+        assert_eq!(contiguous_sum(nums, 127)?, Some(&nums[2..6]));
+        assert_eq!(contiguous_sum(nums, -5)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn negative_numbers() -> Res<()> {
+        let xmas = read_input("preamble: 2\n-1\n3\n2\n7\n".as_bytes())?;
+        assert_eq!(Day9::part1(&xmas)?, 7);
+        let e = Day9::part2(&xmas).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Can't find a contiguous sum with a negative number: -1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part1() -> Res<()> {
+        let xmas = read_input(open_input(9)?)?;
+        assert_eq!(xmas.preamble, PREAMBLE);
+        assert_eq!(Day9::part1(&xmas)?, 675280050);
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> Res<()> {
+        let xmas = read_input(open_input(9)?)?;
+        assert_eq!(Day9::part2(&xmas)?, 96081673);
+        Ok(())
+    }
+}
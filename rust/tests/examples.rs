@@ -0,0 +1,56 @@
+//! Run every registered day against the small worked examples in `tests/examples`.
+
+use aoc_2020::manifest::Manifest;
+use aoc_2020::{open_file, Res, DAYS};
+use std::path::PathBuf;
+
+fn examples_dir() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", "examples"]
+        .iter()
+        .collect()
+}
+
+#[test]
+fn examples() -> Res<()> {
+    let dir = examples_dir();
+    let manifest = Manifest::load(&dir.join("answers"))?;
+
+    let mut failures = vec![];
+    for day in &DAYS {
+        let n = day.number;
+        assert!(
+            manifest.expected(n, 1).is_some(),
+            "No example for day {}",
+            n
+        );
+
+        let puzzle = day.parse(&mut open_file(&dir.join(n.to_string()))?)?;
+
+        // Only run the parts with an expected answer, since the others may be slow.
+        let expected = [manifest.expected(n, 1), manifest.expected(n, 2)];
+        for (i, expected) in expected.iter().enumerate() {
+            let expected = match expected {
+                Some(e) => e,
+                None => continue,
+            };
+            let actual = match i {
+                0 => puzzle.part1(),
+                _ => puzzle.part2(),
+            };
+            match actual {
+                Ok(ans) if ans.to_string() == *expected => (),
+                Ok(ans) => failures.push(format!(
+                    "{}.{}: expected {}, got {}",
+                    n,
+                    i + 1,
+                    expected,
+                    ans
+                )),
+                Err(e) => failures.push(format!("{}.{}: {}", n, i + 1, e)),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    Ok(())
+}
@@ -0,0 +1,24 @@
+//! Compare the Rust solutions against the reference Python scripts, on the checked-in inputs.
+//!
+//! Days whose script can't run here, e.g. because python3 or a module is missing, are skipped.
+
+use aoc_2020::python::{self, Verdict};
+use aoc_2020::{input_path, Res};
+
+/// Day 23's script takes a while for part 2, so it's left to `--cross-check`.
+const SLOW: [u32; 1] = [23];
+
+#[test]
+fn python_agrees() -> Res<()> {
+    let mut failures = vec![];
+    for day in (1..=25).filter(|d| python::has_script(*d) && !SLOW.contains(d)) {
+        match python::cross_check(day, &input_path(day))? {
+            v @ Verdict::Disagree(_) => failures.push(format!("{}: {}", day, v)),
+            Verdict::Skipped(reason) => eprintln!("Skipping day {}: {}", day, reason),
+            Verdict::Agree => (),
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    Ok(())
+}